@@ -7,7 +7,7 @@ use nalgebra::{point, Isometry2, Point2, UnitComplex, Vector2};
 use ordered_float::NotNan;
 use types::{
     configuration::LookAction as LookActionConfiguration, BallState, CycleTime, FieldDimensions,
-    Obstacle, ObstacleKind, PointOfInterest,
+    FieldHalf, Obstacle, ObstacleKind, PointOfInterest,
 };
 
 pub struct ActiveVision {
@@ -131,22 +131,10 @@ fn generate_field_mark_positions(field_dimensions: &FieldDimensions) -> Vec<Poin
     let right_center_circle_junction = point![0.0, -field_dimensions.center_circle_diameter / 2.0];
     let left_center_t_junction = point![0.0, field_dimensions.width / 2.0];
     let right_center_t_junction = point![0.0, -field_dimensions.width / 2.0];
-    let left_opponent_penalty_box_corner = point![
-        field_dimensions.length / 2.0 - field_dimensions.penalty_area_length,
-        field_dimensions.penalty_area_width / 2.0
-    ];
-    let right_opponent_penalty_box_corner = point![
-        field_dimensions.length / 2.0 - field_dimensions.penalty_area_length,
-        -field_dimensions.penalty_area_width / 2.0
-    ];
-    let left_own_penalty_box_corner = point![
-        -field_dimensions.length / 2.0 + field_dimensions.penalty_area_length,
-        field_dimensions.penalty_area_width / 2.0
-    ];
-    let right_own_penalty_box_corner = point![
-        -field_dimensions.length / 2.0 + field_dimensions.penalty_area_length,
-        -field_dimensions.penalty_area_width / 2.0
-    ];
+    let [_, _, left_opponent_penalty_box_corner, right_opponent_penalty_box_corner] =
+        field_dimensions.penalty_box_corners(FieldHalf::Opponent);
+    let [_, _, left_own_penalty_box_corner, right_own_penalty_box_corner] =
+        field_dimensions.penalty_box_corners(FieldHalf::Own);
     vec![
         left_center_circle_junction,
         right_center_circle_junction,
@@ -4,8 +4,8 @@ use framework::AdditionalOutput;
 use nalgebra::{distance, point, vector, Isometry2, Point2};
 use spl_network_messages::{GamePhase, SubState, Team};
 use types::{
-    configuration::RolePositions, rotate_towards, BallState, FieldDimensions, GameControllerState,
-    Line, MotionCommand, PathObstacle, Side, WorldState,
+    configuration::RolePositions, rotate_towards, BallState, FieldDimensions, FieldHalf,
+    GameControllerState, Line, MotionCommand, PathObstacle, Side, WorldState,
 };
 
 use super::{head::LookAction, walk_to_pose::WalkAndStand};
@@ -157,7 +157,8 @@ fn defend_penalty_kick(
         .unwrap_or_else(|| BallState::new_at_center(robot_to_field));
 
     let position_to_defend = point![
-        (-field_dimensions.length + field_dimensions.penalty_area_length) / 2.0,
+        (-field_dimensions.length + field_dimensions.half(FieldHalf::Own).penalty_area_length)
+            / 2.0,
         0.0
     ];
     let mut distance_to_target = if ball.field_side == Side::Left {
@@ -297,9 +298,10 @@ fn penalty_kick_defender_radius(
         ..
     }) = game_controller_state
     {
-        let half_penalty_width = field_dimensions.penalty_area_width / 2.0;
+        let own_half = field_dimensions.half(FieldHalf::Own);
+        let half_penalty_width = own_half.penalty_area_width / 2.0;
         let minimum_penalty_defender_radius =
-            vector![field_dimensions.penalty_area_length, half_penalty_width].norm();
+            vector![own_half.penalty_area_length, half_penalty_width].norm();
         distance_to_target.max(minimum_penalty_defender_radius)
     } else {
         distance_to_target
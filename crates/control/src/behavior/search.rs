@@ -1,8 +1,8 @@
 use framework::AdditionalOutput;
 use nalgebra::{point, Isometry2, Point2, UnitComplex};
 use types::{
-    configuration::Search as SearchConfiguration, FieldDimensions, HeadMotion, MotionCommand,
-    OrientationMode, PathObstacle, Side, WorldState,
+    configuration::Search as SearchConfiguration, FieldDimensions, FieldHalf, HeadMotion,
+    MotionCommand, OrientationMode, PathObstacle, Side, WorldState,
 };
 
 use super::walk_to_pose::{WalkAndStand, WalkPathPlanner};
@@ -21,18 +21,20 @@ impl SearchRole {
         robot_to_field: Isometry2<f32>,
         field_dimensions: &FieldDimensions,
     ) -> Point2<f32> {
+        let own_half = field_dimensions.half(FieldHalf::Own);
+        let opponent_half = field_dimensions.half(FieldHalf::Opponent);
         let goal = point![-field_dimensions.length / 2.0, 0.0];
         let defending_left = point![
-            -field_dimensions.length / 2.0 + field_dimensions.goal_box_area_length + 0.2,
-            field_dimensions.goal_box_area_width / 2.0
+            -field_dimensions.length / 2.0 + own_half.goal_box_area_length + 0.2,
+            own_half.goal_box_area_width / 2.0
         ];
         let defending_right = point![
-            -field_dimensions.length / 2.0 + field_dimensions.penalty_area_length + 0.2,
-            -field_dimensions.penalty_area_width / 2.0
+            -field_dimensions.length / 2.0 + own_half.penalty_area_length + 0.2,
+            -own_half.penalty_area_width / 2.0
         ];
         let center = point![0.0, 0.0];
         let aggressive = point![
-            field_dimensions.length / 2.0 - field_dimensions.penalty_area_length,
+            field_dimensions.length / 2.0 - opponent_half.penalty_area_length,
             0.0
         ];
 
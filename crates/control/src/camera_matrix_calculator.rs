@@ -5,7 +5,7 @@ use nalgebra::{point, vector, Isometry3, UnitQuaternion, Vector3};
 use projection::Projection;
 use types::{
     configuration::CameraMatrixParameters, CameraMatrices, CameraMatrix, CameraPosition,
-    FieldDimensions, Line, Line2, ProjectedFieldLines, RobotDimensions, RobotKinematics,
+    FieldDimensions, FieldHalf, Line, Line2, ProjectedFieldLines, RobotDimensions, RobotKinematics,
 };
 
 pub struct CameraMatrixCalculator {}
@@ -119,27 +119,13 @@ fn project_penalty_area_on_images(
 ) -> Option<Vec<Line2>> {
     let field_length = &field_dimensions.length;
     let field_width = &field_dimensions.width;
-    let penalty_area_length = &field_dimensions.penalty_area_length;
-    let penalty_area_width = &field_dimensions.penalty_area_width;
 
-    let penalty_top_left = camera_matrix
-        .ground_to_pixel(point![field_length / 2.0, penalty_area_width / 2.0])
-        .ok()?;
-    let penalty_top_right = camera_matrix
-        .ground_to_pixel(point![field_length / 2.0, -penalty_area_width / 2.0])
-        .ok()?;
-    let penalty_bottom_left = camera_matrix
-        .ground_to_pixel(point![
-            field_length / 2.0 - penalty_area_length,
-            penalty_area_width / 2.0
-        ])
-        .ok()?;
-    let penalty_bottom_right = camera_matrix
-        .ground_to_pixel(point![
-            field_length / 2.0 - penalty_area_length,
-            -penalty_area_width / 2.0
-        ])
-        .ok()?;
+    let [penalty_top_left, penalty_top_right, penalty_bottom_left, penalty_bottom_right] =
+        field_dimensions.penalty_box_corners(FieldHalf::Opponent);
+    let penalty_top_left = camera_matrix.ground_to_pixel(penalty_top_left).ok()?;
+    let penalty_top_right = camera_matrix.ground_to_pixel(penalty_top_right).ok()?;
+    let penalty_bottom_left = camera_matrix.ground_to_pixel(penalty_bottom_left).ok()?;
+    let penalty_bottom_right = camera_matrix.ground_to_pixel(penalty_bottom_right).ok()?;
     let corner_left = camera_matrix
         .ground_to_pixel(point![field_length / 2.0, field_width / 2.0])
         .ok()?;
@@ -8,8 +8,8 @@ use nalgebra::{distance, point, vector, Isometry2, Point2, UnitComplex, Vector2}
 use ordered_float::NotNan;
 use types::{
     configuration::{FindKickTargets, InWalkKickInfo, InWalkKicks},
-    rotate_towards, BallState, Circle, FieldDimensions, KickDecision, KickTarget, KickVariant,
-    LineSegment, Obstacle, Side, TwoLineSegments,
+    rotate_towards, BallState, Circle, FieldDimensions, FieldHalf, KickDecision, KickTarget,
+    KickVariant, LineSegment, Obstacle, Side, TwoLineSegments,
 };
 
 pub struct KickSelector {}
@@ -197,9 +197,10 @@ fn generate_decisions_for_instant_kicks(
             let is_target_closer_to_opponent_goal = (distance(&target, &opponent_goal_center)
                 + closer_threshold)
                 < distance(&ball_position, &opponent_goal_center);
+            let own_half = field_dimensions.half(FieldHalf::Own);
             let goal_box_radius = vector![
-                field_dimensions.goal_box_area_length,
-                field_dimensions.goal_box_area_width / 2.0
+                own_half.goal_box_area_length,
+                own_half.goal_box_area_width / 2.0
             ]
             .norm();
             let is_ball_close_to_own_goal =
@@ -15,8 +15,8 @@ use types::{
     field_marks_from_field_dimensions,
     localization::{ScoredPose, Update},
     multivariate_normal_distribution::MultivariateNormalDistribution,
-    CorrespondencePoints, Direction, FieldDimensions, FieldMark, GameControllerState, InitialPose,
-    Line, Line2, LineData, Players, PrimaryState, Side,
+    CorrespondencePoints, Direction, FieldDimensions, FieldHalf, FieldMark, GameControllerState,
+    InitialPose, Line, Line2, LineData, Players, PrimaryState, Side,
 };
 
 pub struct Localization {
@@ -56,6 +56,7 @@ pub struct CreationContext {
     pub player_number: Parameter<PlayerNumber, "player_number">,
     pub score_per_good_match: Parameter<f32, "localization.score_per_good_match">,
     pub use_line_measurements: Parameter<bool, "localization.use_line_measurements">,
+    pub use_penalty_marker_crosses: Parameter<bool, "localization.use_penalty_marker_crosses">,
 
     pub robot_to_field: PersistentState<Isometry2<f32>, "robot_to_field">,
 }
@@ -97,6 +98,7 @@ pub struct CycleContext {
         Parameter<usize, "localization.maximum_amount_of_gradient_descent_iterations">,
     pub maximum_amount_of_outer_iterations:
         Parameter<usize, "localization.maximum_amount_of_outer_iterations">,
+    pub maximum_cross_center_distance: Parameter<f32, "localization.maximum_cross_center_distance">,
     pub minimum_fit_error: Parameter<f32, "localization.minimum_fit_error">,
     pub odometry_noise: Parameter<Vector3<f32>, "localization.odometry_noise">,
     pub player_number: Parameter<PlayerNumber, "player_number">,
@@ -118,12 +120,15 @@ pub struct MainOutputs {
 impl Localization {
     pub fn new(context: CreationContext) -> Result<Self> {
         Ok(Self {
-            field_marks: field_marks_from_field_dimensions(context.field_dimensions)
-                .into_iter()
-                .chain(goal_support_structure_line_marks_from_field_dimensions(
-                    context.field_dimensions,
-                ))
-                .collect(),
+            field_marks: field_marks_from_field_dimensions(
+                context.field_dimensions,
+                *context.use_penalty_marker_crosses,
+            )
+            .into_iter()
+            .chain(goal_support_structure_line_marks_from_field_dimensions(
+                context.field_dimensions,
+            ))
+            .collect(),
             last_primary_state: PrimaryState::Unstiff,
             hypotheses: vec![],
             hypotheses_when_entered_playing: vec![],
@@ -160,7 +165,10 @@ impl Localization {
                 }),
             ) => {
                 let penalty_shoot_out_striker_pose = Isometry2::translation(
-                    -context.field_dimensions.penalty_area_length
+                    -context
+                        .field_dimensions
+                        .half(FieldHalf::Opponent)
+                        .penalty_area_length
                         + (context.field_dimensions.length / 2.0),
                     0.0,
                 );
@@ -358,10 +366,12 @@ impl Localization {
                                 robot_to_field,
                                 field_mark_correspondence,
                             ),
-                            FieldMark::Circle { .. } => get_2d_translation_measurement(
-                                robot_to_field,
-                                field_mark_correspondence,
-                            ),
+                            FieldMark::Circle { .. } | FieldMark::Cross { .. } => {
+                                get_2d_translation_measurement(
+                                    robot_to_field,
+                                    field_mark_correspondence,
+                                )
+                            }
                         };
                         let line_length = field_mark_correspondence.measured_line_in_field.length();
                         let line_length_weight = if line_length == 0.0 {
@@ -390,7 +400,7 @@ impl Localization {
                                                 update.y,
                                             ),
                                         },
-                                        FieldMark::Circle { .. } => {
+                                        FieldMark::Circle { .. } | FieldMark::Cross { .. } => {
                                             Isometry2::new(update, robot_to_field.rotation.angle())
                                         }
                                     };
@@ -426,7 +436,7 @@ impl Localization {
                                     },
                                 )
                                 .context("Failed to update pose filter")?,
-                            FieldMark::Circle { .. } => scored_state
+                            FieldMark::Circle { .. } | FieldMark::Cross { .. } => scored_state
                                 .state
                                 .update_with_2d_translation(
                                     update,
@@ -637,6 +647,7 @@ fn get_fitted_field_mark_correspondence(
             correction,
             field_marks,
             *context.line_length_acceptance_factor,
+            *context.maximum_cross_center_distance,
         ));
 
         let weight_matrices: Vec<_> = correspondence_points
@@ -710,6 +721,7 @@ fn get_fitted_field_mark_correspondence(
         correction,
         field_marks,
         *context.line_length_acceptance_factor,
+        *context.maximum_cross_center_distance,
     );
 
     let correspondence_points = get_correspondence_points(field_mark_correspondences.clone());
@@ -755,6 +767,7 @@ fn get_field_mark_correspondence(
     correction: Isometry2<f32>,
     field_marks: &[FieldMark],
     line_length_acceptance_factor: f32,
+    maximum_cross_center_distance: f32,
 ) -> Vec<FieldMarkCorrespondence> {
     measured_lines_in_field
         .iter()
@@ -763,12 +776,26 @@ fn get_field_mark_correspondence(
                 .iter()
                 .filter_map(|field_mark| {
                     let transformed_line = correction * measured_line_in_field;
-                    let field_mark_length = match field_mark {
-                        FieldMark::Line { line, direction: _ } => line.length(),
-                        FieldMark::Circle { center: _, radius } => *radius, // approximation
-                    };
                     let measured_line_length = transformed_line.length();
-                    if measured_line_length <= field_mark_length * line_length_acceptance_factor {
+                    let (is_accepted, length_weight) = match field_mark {
+                        FieldMark::Line { line, direction: _ } => accept_by_length(
+                            measured_line_length,
+                            line.length(),
+                            line_length_acceptance_factor,
+                        ),
+                        FieldMark::Circle { center: _, radius } => accept_by_length(
+                            measured_line_length,
+                            *radius, // approximation
+                            line_length_acceptance_factor,
+                        ),
+                        // a cross is too small to compare lengths, it is matched by its center
+                        FieldMark::Cross { center } => (
+                            distance(&transformed_line.center(), center)
+                                <= maximum_cross_center_distance,
+                            1.0,
+                        ),
+                    };
+                    if is_accepted {
                         let correspondences = field_mark.to_correspondence_points(transformed_line);
                         assert_relative_eq!(
                             correspondences.measured_direction.norm(),
@@ -780,13 +807,7 @@ fn get_field_mark_correspondence(
                             1.0,
                             epsilon = 0.0001
                         );
-                        let angle_weight = correspondences
-                            .measured_direction
-                            .dot(&correspondences.reference_direction)
-                            .abs()
-                            + measured_line_length / field_mark_length;
-                        assert!(field_mark_length != 0.0);
-                        let length_weight = measured_line_length / field_mark_length; // TODO: this will penalize center circle lines because field_mark_length is only approximated
+                        let angle_weight = correspondences.direction_agreement + length_weight;
                         let weight = angle_weight + length_weight;
                         if weight != 0.0 {
                             Some((correspondences, weight, field_mark, transformed_line))
@@ -834,6 +855,21 @@ fn get_field_mark_correspondence(
         .collect()
 }
 
+/// Whether a measured line is short enough for a field mark and its length weight
+fn accept_by_length(
+    measured_line_length: f32,
+    field_mark_length: f32,
+    line_length_acceptance_factor: f32,
+) -> (bool, f32) {
+    assert!(field_mark_length != 0.0);
+    // TODO: this will penalize center circle lines because field_mark_length is only approximated
+    let length_weight = measured_line_length / field_mark_length;
+    (
+        measured_line_length <= field_mark_length * line_length_acceptance_factor,
+        length_weight,
+    )
+}
+
 fn get_correspondence_points(
     field_mark_correspondences: Vec<FieldMarkCorrespondence>,
 ) -> Vec<CorrespondencePoints> {
@@ -1420,6 +1456,7 @@ mod tests {
     #[test]
     fn correct_correspondence_points() {
         let line_length_acceptance_factor = 1.5;
+        let maximum_cross_center_distance = 0.3;
 
         let measured_lines_in_field = [Line(point![0.0, 0.0], point![1.0, 0.0])];
         let field_marks = [FieldMark::Line {
@@ -1431,6 +1468,7 @@ mod tests {
             Isometry2::identity(),
             &field_marks,
             line_length_acceptance_factor,
+            maximum_cross_center_distance,
         );
         assert_eq!(correspondences.len(), 1);
         assert_relative_eq!(
@@ -1460,6 +1498,7 @@ mod tests {
             Isometry2::identity(),
             &field_marks,
             line_length_acceptance_factor,
+            maximum_cross_center_distance,
         );
         assert_eq!(correspondences.len(), 1);
         assert_relative_eq!(
@@ -1489,6 +1528,7 @@ mod tests {
             Isometry2::new(vector![0.0, 1.0], 0.0),
             &field_marks,
             line_length_acceptance_factor,
+            maximum_cross_center_distance,
         );
         assert_eq!(correspondences.len(), 1);
         assert_relative_eq!(
@@ -1509,6 +1549,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cross_mark_correspondence_matches_by_center_distance() {
+        let field_marks = [FieldMark::Cross {
+            center: point![-3.2, 0.0],
+        }];
+
+        let measured_lines_in_field = [Line(point![-3.35, 0.1], point![-3.15, 0.1])];
+        let correspondences = get_field_mark_correspondence(
+            &measured_lines_in_field,
+            Isometry2::identity(),
+            &field_marks,
+            1.5,
+            0.3,
+        );
+        assert_eq!(correspondences.len(), 1);
+        assert_relative_eq!(
+            correspondences[0].correspondence_points.0.measured,
+            point![-3.35, 0.1]
+        );
+        assert_relative_eq!(
+            correspondences[0].correspondence_points.0.reference,
+            point![-3.3, 0.0],
+            epsilon = 0.0001
+        );
+        assert_relative_eq!(
+            correspondences[0].correspondence_points.1.reference,
+            point![-3.1, 0.0],
+            epsilon = 0.0001
+        );
+
+        let measured_lines_in_field = [Line(point![-2.85, 0.1], point![-2.65, 0.1])];
+        let correspondences = get_field_mark_correspondence(
+            &measured_lines_in_field,
+            Isometry2::identity(),
+            &field_marks,
+            1.5,
+            0.3,
+        );
+        assert!(correspondences.is_empty());
+    }
+
     #[test]
     fn circle_mark_correspondence_translates() {
         let robot_to_field = Isometry2::identity();
@@ -63,3 +63,72 @@ impl JumpLeft {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::*;
+
+    fn cycle_with_motion(
+        jump_left: &mut JumpLeft,
+        motion_safe_exits: &mut MotionSafeExits,
+        current_motion: MotionType,
+    ) -> JointsCommand<f32> {
+        jump_left
+            .cycle(CycleContext {
+                motion_safe_exits,
+                condition_input: &ConditionInput::default(),
+                cycle_time: &CycleTime {
+                    start_time: UNIX_EPOCH,
+                    last_cycle_duration: Duration::from_millis(12),
+                },
+                motion_selection: &MotionSelection {
+                    current_motion,
+                    dispatching_motion: None,
+                },
+                sensor_data: &SensorData::default(),
+            })
+            .unwrap()
+            .jump_left_joints_command
+            .value
+    }
+
+    #[test]
+    fn interpolator_only_advances_while_jump_left_is_selected() {
+        let mut jump_left = JumpLeft {
+            interpolator: MotionFile::from_path(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../../etc/motions/jump_left.json"
+            ))
+            .unwrap()
+            .try_into()
+            .unwrap(),
+        };
+        let mut motion_safe_exits = MotionSafeExits::default();
+
+        let initial_command = cycle_with_motion(
+            &mut jump_left,
+            &mut motion_safe_exits,
+            MotionType::JumpRight,
+        );
+        for _ in 0..200 {
+            let command = cycle_with_motion(
+                &mut jump_left,
+                &mut motion_safe_exits,
+                MotionType::JumpRight,
+            );
+            assert_eq!(command.positions, initial_command.positions);
+            assert!(!motion_safe_exits[MotionType::JumpLeft]);
+        }
+
+        for _ in 0..200 {
+            cycle_with_motion(&mut jump_left, &mut motion_safe_exits, MotionType::JumpLeft);
+        }
+        assert!(motion_safe_exits[MotionType::JumpLeft]);
+
+        let command = cycle_with_motion(&mut jump_left, &mut motion_safe_exits, MotionType::Stand);
+        assert_eq!(command.positions, initial_command.positions);
+        assert!(!motion_safe_exits[MotionType::JumpLeft]);
+    }
+}
@@ -63,3 +63,76 @@ impl JumpRight {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::*;
+
+    fn cycle_with_motion(
+        jump_right: &mut JumpRight,
+        motion_safe_exits: &mut MotionSafeExits,
+        current_motion: MotionType,
+    ) -> JointsCommand<f32> {
+        jump_right
+            .cycle(CycleContext {
+                motion_safe_exits,
+                condition_input: &ConditionInput::default(),
+                cycle_time: &CycleTime {
+                    start_time: UNIX_EPOCH,
+                    last_cycle_duration: Duration::from_millis(12),
+                },
+                motion_selection: &MotionSelection {
+                    current_motion,
+                    dispatching_motion: None,
+                },
+                sensor_data: &SensorData::default(),
+            })
+            .unwrap()
+            .jump_right_joints_command
+            .value
+    }
+
+    #[test]
+    fn interpolator_only_advances_while_jump_right_is_selected() {
+        let mut jump_right = JumpRight {
+            interpolator: MotionFile::from_path(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../../etc/motions/jump_left.json"
+            ))
+            .unwrap()
+            .try_into()
+            .unwrap(),
+        };
+        let mut motion_safe_exits = MotionSafeExits::default();
+
+        let initial_command = cycle_with_motion(
+            &mut jump_right,
+            &mut motion_safe_exits,
+            MotionType::JumpLeft,
+        );
+        for _ in 0..200 {
+            let command = cycle_with_motion(
+                &mut jump_right,
+                &mut motion_safe_exits,
+                MotionType::JumpLeft,
+            );
+            assert_eq!(command.positions, initial_command.positions);
+            assert!(!motion_safe_exits[MotionType::JumpRight]);
+        }
+
+        for _ in 0..200 {
+            cycle_with_motion(
+                &mut jump_right,
+                &mut motion_safe_exits,
+                MotionType::JumpRight,
+            );
+        }
+        assert!(motion_safe_exits[MotionType::JumpRight]);
+
+        let command = cycle_with_motion(&mut jump_right, &mut motion_safe_exits, MotionType::Stand);
+        assert_eq!(command.positions, initial_command.positions);
+        assert!(!motion_safe_exits[MotionType::JumpRight]);
+    }
+}
@@ -113,11 +113,12 @@ impl ObstacleFilter {
                 let measured_positions_in_control_cycle = feet_top
                     .iter()
                     .chain(feet_bottom.iter())
-                    .flat_map(|obstacles| obstacles.positions.iter());
+                    .flat_map(|detected_feet| detected_feet.feet.iter())
+                    .map(|foot| foot.position_in_ground);
 
                 for position in measured_positions_in_control_cycle {
                     self.update_hypotheses_with_measurement(
-                        *position,
+                        position,
                         ObstacleKind::Robot,
                         *detection_time,
                         context
@@ -4,8 +4,8 @@ use framework::MainOutput;
 use nalgebra::{point, vector, Point2};
 use spl_network_messages::{GameState, SubState, Team};
 use types::{
-    BallState, Circle, FieldDimensions, FilteredGameState, GameControllerState, Rectangle,
-    RuleObstacle,
+    BallState, Circle, FieldDimensions, FieldHalf, FilteredGameState, GameControllerState,
+    Rectangle, RuleObstacle,
 };
 
 pub struct RuleObstacleComposer {}
@@ -105,20 +105,18 @@ impl RuleObstacleComposer {
 }
 
 pub fn create_penalty_box(field_dimensions: &FieldDimensions, kicking_team: Team) -> RuleObstacle {
-    let side_factor: f32 = match kicking_team {
-        Team::Hulks => 1.0,
-        Team::Opponent => -1.0,
+    let (side_factor, field_half): (f32, _) = match kicking_team {
+        Team::Hulks => (1.0, FieldHalf::Opponent),
+        Team::Opponent => (-1.0, FieldHalf::Own),
         // Striker may still enter opponent penalty box so this doesn't stop us from defending our own goal
-        Team::Uncertain => 1.0,
+        Team::Uncertain => (1.0, FieldHalf::Opponent),
     };
+    let half = field_dimensions.half(field_half);
     let half_field_length = field_dimensions.length / 2.0;
-    let half_penalty_area_length = field_dimensions.penalty_area_length / 2.0;
+    let half_penalty_area_length = half.penalty_area_length / 2.0;
     let center_x = side_factor * (half_field_length - half_penalty_area_length);
     RuleObstacle::Rectangle(Rectangle::new_with_center_and_size(
         point![center_x, 0.0],
-        vector![
-            field_dimensions.penalty_area_length,
-            field_dimensions.penalty_area_width
-        ],
+        vector![half.penalty_area_length, half.penalty_area_width],
     ))
 }
@@ -43,37 +43,103 @@ impl WhistleFilter {
     }
 
     pub fn cycle(&mut self, context: CycleContext) -> Result<MainOutputs> {
-        let cycle_start_time = context.cycle_time.start_time;
-
-        for &is_detected in context
+        let detections = context
             .detected_whistle
             .persistent
             .values()
             .flatten()
             .flat_map(|whistle| &whistle.is_detected)
-        {
+            .copied();
+        let filtered_whistle = self.update(
+            detections,
+            *context.buffer_length,
+            *context.minimum_detections,
+            context.cycle_time.start_time,
+        );
+
+        Ok(MainOutputs {
+            filtered_whistle: filtered_whistle.into(),
+        })
+    }
+
+    fn update(
+        &mut self,
+        detections: impl IntoIterator<Item = bool>,
+        buffer_length: usize,
+        minimum_detections: usize,
+        cycle_start_time: SystemTime,
+    ) -> FilteredWhistle {
+        for is_detected in detections {
             self.detection_buffer.push_front(is_detected);
         }
-        self.detection_buffer.truncate(*context.buffer_length);
+        self.detection_buffer.truncate(buffer_length);
         let number_of_detections = self
             .detection_buffer
             .iter()
             .filter(|&&was_detected| was_detected)
             .count();
-        let is_detected = number_of_detections > *context.minimum_detections;
+        let is_detected = number_of_detections >= minimum_detections;
         let started_this_cycle = is_detected && !self.was_detected_last_cycle;
         if started_this_cycle {
             self.last_detection = Some(cycle_start_time);
         }
         self.was_detected_last_cycle = is_detected;
 
-        Ok(MainOutputs {
-            filtered_whistle: FilteredWhistle {
-                is_detected,
-                last_detection: self.last_detection,
-                started_this_cycle,
-            }
-            .into(),
-        })
+        FilteredWhistle {
+            is_detected,
+            last_detection: self.last_detection,
+            started_this_cycle,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn whistle_filter() -> WhistleFilter {
+        WhistleFilter {
+            detection_buffer: Default::default(),
+            was_detected_last_cycle: false,
+            last_detection: None,
+        }
+    }
+
+    #[test]
+    fn detects_whistle_at_minimum_detections() {
+        let mut filter = whistle_filter();
+        let first_cycle = SystemTime::UNIX_EPOCH + Duration::from_secs(1);
+        let second_cycle = first_cycle + Duration::from_millis(12);
+
+        let filtered_whistle = filter.update([true, false, true], 5, 3, first_cycle);
+        assert!(!filtered_whistle.is_detected);
+        assert!(!filtered_whistle.started_this_cycle);
+        assert_eq!(filtered_whistle.last_detection, None);
+
+        let filtered_whistle = filter.update([true], 5, 3, second_cycle);
+        assert!(filtered_whistle.is_detected);
+        assert!(filtered_whistle.started_this_cycle);
+        assert_eq!(filtered_whistle.last_detection, Some(second_cycle));
+    }
+
+    #[test]
+    fn detection_ends_when_positives_leave_the_buffer() {
+        let mut filter = whistle_filter();
+        let first_cycle = SystemTime::UNIX_EPOCH + Duration::from_secs(1);
+        let second_cycle = first_cycle + Duration::from_millis(12);
+        let third_cycle = second_cycle + Duration::from_millis(12);
+
+        assert!(filter.update([true, true], 3, 2, first_cycle).is_detected);
+
+        let filtered_whistle = filter.update([false], 3, 2, second_cycle);
+        assert!(filtered_whistle.is_detected);
+        assert!(!filtered_whistle.started_this_cycle);
+        assert_eq!(filtered_whistle.last_detection, Some(first_cycle));
+
+        let filtered_whistle = filter.update([false], 3, 2, third_cycle);
+        assert!(!filtered_whistle.is_detected);
+        assert_eq!(filtered_whistle.last_detection, Some(first_cycle));
     }
 }
@@ -15,3 +15,50 @@ pub fn variance(data: &[f32], mean: f32) -> f32 {
 pub fn standard_deviation(data: &[f32], mean: f32) -> f32 {
     variance(data, mean).sqrt()
 }
+
+/// Counts `values` in `bins` equally wide bins spanning their minimum and maximum
+///
+/// Returns the counts together with the minimum and maximum, or `None` if there are no values or
+/// no bins. The maximum is counted in the last bin.
+pub fn histogram(values: &[f32], bins: usize) -> Option<(Vec<u32>, f32, f32)> {
+    if values.is_empty() || bins == 0 {
+        return None;
+    }
+    let minimum = values.iter().copied().fold(f32::INFINITY, f32::min);
+    let maximum = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let bin_width = (maximum - minimum) / bins as f32;
+    let mut counts = vec![0; bins];
+    for value in values {
+        let bin = if bin_width > 0.0 {
+            (((value - minimum) / bin_width) as usize).min(bins - 1)
+        } else {
+            0
+        };
+        counts[bin] += 1;
+    }
+    Some((counts, minimum, maximum))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histogram_of_empty_values() {
+        assert_eq!(histogram(&[], 5), None);
+        assert_eq!(histogram(&[1.0, 2.0], 0), None);
+    }
+
+    #[test]
+    fn histogram_of_single_value() {
+        assert_eq!(histogram(&[4.2], 3), Some((vec![1, 0, 0], 4.2, 4.2)));
+        assert_eq!(histogram(&[4.2, 4.2], 3), Some((vec![2, 0, 0], 4.2, 4.2)));
+    }
+
+    #[test]
+    fn histogram_of_typical_values() {
+        let values = [0.0, 0.5, 1.0, 2.5, 3.0, 3.9, 4.0];
+
+        assert_eq!(histogram(&values, 4), Some((vec![2, 1, 1, 3], 0.0, 4.0)));
+    }
+}
@@ -1,4 +1,4 @@
-use nalgebra::{point, vector, Point2, Point3, Vector2, Vector3};
+use nalgebra::{point, vector, Point2, Point3, Rotation3, Vector2, Vector3};
 use thiserror::Error;
 use types::CameraMatrix;
 
@@ -16,6 +16,8 @@ pub trait Projection {
     fn pixel_to_camera(&self, pixel_coordinates: Point2<f32>) -> Vector3<f32>;
     fn camera_to_pixel(&self, camera_ray: Vector3<f32>) -> Result<Point2<f32>, Error>;
     fn pixel_to_ground(&self, pixel_coordinates: Point2<f32>) -> Result<Point2<f32>, Error>;
+    /// Projects all pixels to the ground, `None` for pixels that cannot be projected
+    fn pixels_to_ground(&self, pixel_coordinates: &[Point2<f32>]) -> Vec<Option<Point2<f32>>>;
     fn pixel_to_ground_with_z(
         &self,
         pixel_coordinates: Point2<f32>,
@@ -64,26 +66,33 @@ impl Projection for CameraMatrix {
         self.pixel_to_ground_with_z(pixel_coordinates, 0.0)
     }
 
+    fn pixels_to_ground(&self, pixel_coordinates: &[Point2<f32>]) -> Vec<Option<Point2<f32>>> {
+        let camera_to_ground_rotation = self.camera_to_ground.rotation.to_rotation_matrix();
+        pixel_coordinates
+            .iter()
+            .map(|pixel_coordinates| {
+                camera_ray_to_ground_with_z(
+                    &camera_to_ground_rotation,
+                    self.camera_to_ground.translation.vector,
+                    self.pixel_to_camera(*pixel_coordinates),
+                    0.0,
+                )
+                .ok()
+            })
+            .collect()
+    }
+
     fn pixel_to_ground_with_z(
         &self,
         pixel_coordinates: Point2<f32>,
         z: f32,
     ) -> Result<Point2<f32>, Error> {
-        let camera_ray = self.pixel_to_camera(pixel_coordinates);
-        let camera_ray_over_ground = self.camera_to_ground.rotation * camera_ray;
-        if camera_ray_over_ground.z >= 0.0
-            || camera_ray_over_ground.x.is_nan()
-            || camera_ray_over_ground.y.is_nan()
-            || camera_ray_over_ground.z.is_nan()
-        {
-            return Err(Error::AboveHorizon);
-        }
-
-        let distance_to_plane = z - self.camera_to_ground.translation.z;
-        let slope = distance_to_plane / camera_ray_over_ground.z;
-        let intersection_point =
-            self.camera_to_ground.translation.vector + camera_ray_over_ground * slope;
-        Ok(point![intersection_point.x, intersection_point.y])
+        camera_ray_to_ground_with_z(
+            &self.camera_to_ground.rotation.to_rotation_matrix(),
+            self.camera_to_ground.translation.vector,
+            self.pixel_to_camera(pixel_coordinates),
+            z,
+        )
     }
 
     fn ground_to_pixel(&self, ground_coordinates: Point2<f32>) -> Result<Point2<f32>, Error> {
@@ -143,3 +152,25 @@ impl Projection for CameraMatrix {
         Ok(resolution.y as f32 * angle / self.field_of_view.y)
     }
 }
+
+/// Intersects a camera ray with the plane at height `z` above the ground
+fn camera_ray_to_ground_with_z(
+    camera_to_ground_rotation: &Rotation3<f32>,
+    camera_position: Vector3<f32>,
+    camera_ray: Vector3<f32>,
+    z: f32,
+) -> Result<Point2<f32>, Error> {
+    let camera_ray_over_ground = camera_to_ground_rotation * camera_ray;
+    if camera_ray_over_ground.z >= 0.0
+        || camera_ray_over_ground.x.is_nan()
+        || camera_ray_over_ground.y.is_nan()
+        || camera_ray_over_ground.z.is_nan()
+    {
+        return Err(Error::AboveHorizon);
+    }
+
+    let distance_to_plane = z - camera_position.z;
+    let slope = distance_to_plane / camera_ray_over_ground.z;
+    let intersection_point = camera_position + camera_ray_over_ground * slope;
+    Ok(point![intersection_point.x, intersection_point.y])
+}
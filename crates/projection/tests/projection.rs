@@ -359,3 +359,39 @@ fn get_pixel_radius_pitch_45_degree_down() {
         207.69307
     );
 }
+
+#[test]
+fn pixels_to_ground_matches_pixel_to_ground() {
+    let mut camera_matrix = from_normalized_focal_and_center_short(
+        vector![0.95, 1.27],
+        point![0.5, 0.5],
+        vector![640.0, 480.0],
+    );
+    camera_matrix.camera_to_ground.translation = Translation::from(point![0.0, 0.0, 0.5]);
+    camera_matrix.camera_to_ground.rotation =
+        UnitQuaternion::from_euler_angles(0.0, std::f32::consts::PI / 4.0, 0.0);
+    let pixels = [
+        point![320.0, 240.0],
+        point![0.0, 479.0],
+        point![639.0, 300.0],
+        point![100.0, 0.0],
+        point![320.0, -2000.0],
+    ];
+
+    let batch = camera_matrix.pixels_to_ground(&pixels);
+
+    assert_eq!(batch.len(), pixels.len());
+    assert!(batch[..4].iter().all(Option::is_some));
+    assert!(batch[4].is_none());
+    for (pixel, batch_ground) in pixels.iter().zip(batch) {
+        match (camera_matrix.pixel_to_ground(*pixel).ok(), batch_ground) {
+            (Some(single_ground), Some(batch_ground)) => {
+                assert_relative_eq!(single_ground, batch_ground, epsilon = 1e-5)
+            }
+            (None, None) => {}
+            (single_ground, batch_ground) => {
+                panic!("single {single_ground:?} differs from batch {batch_ground:?}")
+            }
+        }
+    }
+}
@@ -80,6 +80,22 @@ impl CameraMatrix {
         }
     }
 
+    /// Topmost image row touched by the horizon, clamped to the top of the image
+    ///
+    /// Returns `None` if the horizon is not finite.
+    pub fn horizon_row(&self) -> Option<u32> {
+        let horizon_y_minimum = self.horizon.horizon_y_minimum();
+        horizon_y_minimum
+            .is_finite()
+            .then(|| horizon_y_minimum.max(0.0) as u32)
+    }
+
+    /// Whether `point` lies on or below the horizon row, every point does without a horizon
+    pub fn below_horizon(&self, point: Point2<f32>) -> bool {
+        self.horizon_row()
+            .map_or(true, |horizon_row| point.y >= horizon_row as f32)
+    }
+
     fn calculate_field_of_view(
         focal_lengths: Vector2<f32>,
         image_size: Vector2<f32>,
@@ -94,10 +110,47 @@ impl CameraMatrix {
 #[cfg(test)]
 mod tests {
     use approx::assert_relative_eq;
-    use nalgebra::vector;
+    use nalgebra::{point, vector};
 
     use super::*;
 
+    fn camera_matrix_with_horizon(left_horizon_y: f32, right_horizon_y: f32) -> CameraMatrix {
+        CameraMatrix {
+            horizon: Horizon {
+                left_horizon_y,
+                right_horizon_y,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn horizon_row_is_topmost_horizon_point() {
+        assert_eq!(
+            camera_matrix_with_horizon(120.5, 80.2).horizon_row(),
+            Some(80)
+        );
+        assert_eq!(
+            camera_matrix_with_horizon(-30.0, 10.0).horizon_row(),
+            Some(0)
+        );
+        assert_eq!(
+            camera_matrix_with_horizon(f32::NAN, f32::NAN).horizon_row(),
+            None
+        );
+    }
+
+    #[test]
+    fn points_below_horizon() {
+        let camera_matrix = camera_matrix_with_horizon(120.5, 80.2);
+        assert!(!camera_matrix.below_horizon(point![10.0, 79.9]));
+        assert!(camera_matrix.below_horizon(point![10.0, 80.0]));
+        assert!(camera_matrix.below_horizon(point![600.0, 300.0]));
+
+        let camera_matrix = camera_matrix_with_horizon(f32::NAN, f32::NAN);
+        assert!(camera_matrix.below_horizon(point![10.0, 0.0]));
+    }
+
     #[test]
     fn check_field_of_view_calculation() {
         // Old implementation, assumes normalized values
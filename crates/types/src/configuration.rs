@@ -349,6 +349,8 @@ pub enum EdgeDetectionSource {
     #[default]
     Luminance,
     GreenChromaticity,
+    SaturationOfHsv,
+    ValueOfHsv,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
@@ -4,7 +4,14 @@ use serialize_hierarchy::SerializeHierarchy;
 
 #[derive(Default, Clone, Debug, Deserialize, Serialize, SerializeHierarchy)]
 pub struct DetectedFeet {
-    pub positions: Vec<Point2<f32>>,
+    pub feet: Vec<DetectedFoot>,
+}
+
+#[derive(Default, Clone, Debug, Deserialize, Serialize, SerializeHierarchy)]
+pub struct DetectedFoot {
+    pub position_in_ground: Point2<f32>,
+    /// Confidence in `(0, 1)`, exactly 1 if `minimum_samples_per_cluster` is zero
+    pub confidence: f32,
 }
 
 #[derive(Default, Clone, Debug, Deserialize, Serialize, SerializeHierarchy)]
@@ -13,6 +20,13 @@ pub struct ClusterPoint {
     pub position_in_ground: Point2<f32>,
 }
 
+#[derive(Default, Clone, Debug, Deserialize, Serialize, SerializeHierarchy)]
+pub struct DetectedFootInImage {
+    pub pixel_coordinates: Point2<f32>,
+    /// Confidence of the corresponding [`DetectedFoot`]
+    pub confidence: f32,
+}
+
 #[derive(Default, Clone, Debug, Deserialize, Serialize, SerializeHierarchy)]
 pub struct CountedCluster {
     pub mean: Point2<f32>,
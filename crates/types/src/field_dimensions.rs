@@ -1,4 +1,4 @@
-use nalgebra::Point2;
+use nalgebra::{point, Point2};
 use serde::{Deserialize, Serialize};
 use serialize_hierarchy::SerializeHierarchy;
 
@@ -19,15 +19,212 @@ pub struct FieldDimensions {
     pub goal_inner_width: f32,
     pub goal_post_diameter: f32,
     pub goal_depth: f32,
+    /// Overrides the goal box and penalty area of the own half (negative x)
+    pub own_half_overrides: Option<HalfDimensions>,
+    /// Overrides the goal box and penalty area of the opponent half (positive x)
+    pub opponent_half_overrides: Option<HalfDimensions>,
+}
+
+/// Half of the field in the field frame, the own half has negative x
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FieldHalf {
+    Own,
+    Opponent,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
+pub struct HalfDimensions {
+    pub goal_box_area_length: f32,
+    pub goal_box_area_width: f32,
+    pub penalty_area_length: f32,
+    pub penalty_area_width: f32,
 }
 
 impl FieldDimensions {
+    pub fn half(&self, field_half: FieldHalf) -> HalfDimensions {
+        match field_half {
+            FieldHalf::Own => self.own_half_overrides,
+            FieldHalf::Opponent => self.opponent_half_overrides,
+        }
+        .unwrap_or_else(|| self.symmetric_half())
+    }
+
+    /// Corners on the goal line followed by the corners facing the center, each pair ordered by
+    /// descending y
+    pub fn penalty_box_corners(&self, field_half: FieldHalf) -> [Point2<f32>; 4] {
+        let half = self.half(field_half);
+        self.box_corners(
+            field_half,
+            half.penalty_area_length,
+            half.penalty_area_width,
+        )
+    }
+
+    /// Corners on the goal line followed by the corners facing the center, each pair ordered by
+    /// descending y
+    pub fn goal_box_corners(&self, field_half: FieldHalf) -> [Point2<f32>; 4] {
+        let half = self.half(field_half);
+        self.box_corners(
+            field_half,
+            half.goal_box_area_length,
+            half.goal_box_area_width,
+        )
+    }
+
+    fn box_corners(&self, field_half: FieldHalf, length: f32, width: f32) -> [Point2<f32>; 4] {
+        let sign = match field_half {
+            FieldHalf::Own => -1.0,
+            FieldHalf::Opponent => 1.0,
+        };
+        let goal_line_x = sign * self.length / 2.0;
+        let inner_x = sign * (self.length / 2.0 - length);
+        [
+            point![goal_line_x, width / 2.0],
+            point![goal_line_x, -width / 2.0],
+            point![inner_x, width / 2.0],
+            point![inner_x, -width / 2.0],
+        ]
+    }
+
+    fn symmetric_half(&self) -> HalfDimensions {
+        HalfDimensions {
+            goal_box_area_length: self.goal_box_area_length,
+            goal_box_area_width: self.goal_box_area_width,
+            penalty_area_length: self.penalty_area_length,
+            penalty_area_width: self.penalty_area_width,
+        }
+    }
+
     pub fn is_inside_field(&self, position: Point2<f32>) -> bool {
         position.x.abs() < self.length / 2.0 && position.y.abs() < self.width / 2.0
     }
 
     pub fn is_inside_any_goal_box(&self, position: Point2<f32>) -> bool {
-        position.x.abs() > self.length / 2.0 - self.goal_box_area_length
-            && position.y.abs() < self.goal_box_area_width / 2.0
+        let field_half = if position.x < 0.0 {
+            FieldHalf::Own
+        } else {
+            FieldHalf::Opponent
+        };
+        let half = self.half(field_half);
+        position.x.abs() > self.length / 2.0 - half.goal_box_area_length
+            && position.y.abs() < half.goal_box_area_width / 2.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    fn assert_corners_eq(actual: [Point2<f32>; 4], expected: [Point2<f32>; 4]) {
+        for (actual, expected) in actual.into_iter().zip(expected) {
+            assert_relative_eq!(actual, expected, epsilon = 1e-5);
+        }
+    }
+
+    fn standard_field_dimensions() -> FieldDimensions {
+        FieldDimensions {
+            length: 9.0,
+            width: 6.0,
+            goal_box_area_length: 0.6,
+            goal_box_area_width: 2.2,
+            penalty_area_length: 1.65,
+            penalty_area_width: 4.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn penalty_box_corners_of_standard_field() {
+        let field_dimensions = standard_field_dimensions();
+
+        assert_corners_eq(
+            field_dimensions.penalty_box_corners(FieldHalf::Opponent),
+            [
+                point![4.5, 2.0],
+                point![4.5, -2.0],
+                point![2.85, 2.0],
+                point![2.85, -2.0],
+            ],
+        );
+        assert_corners_eq(
+            field_dimensions.penalty_box_corners(FieldHalf::Own),
+            [
+                point![-4.5, 2.0],
+                point![-4.5, -2.0],
+                point![-2.85, 2.0],
+                point![-2.85, -2.0],
+            ],
+        );
+    }
+
+    #[test]
+    fn goal_box_corners_of_standard_field() {
+        let field_dimensions = standard_field_dimensions();
+
+        assert_corners_eq(
+            field_dimensions.goal_box_corners(FieldHalf::Opponent),
+            [
+                point![4.5, 1.1],
+                point![4.5, -1.1],
+                point![3.9, 1.1],
+                point![3.9, -1.1],
+            ],
+        );
+        assert_corners_eq(
+            field_dimensions.goal_box_corners(FieldHalf::Own),
+            [
+                point![-4.5, 1.1],
+                point![-4.5, -1.1],
+                point![-3.9, 1.1],
+                point![-3.9, -1.1],
+            ],
+        );
+    }
+
+    #[test]
+    fn goal_box_containment_respects_half_overrides() {
+        let field_dimensions = FieldDimensions {
+            own_half_overrides: Some(HalfDimensions {
+                goal_box_area_length: 1.0,
+                goal_box_area_width: 3.0,
+                penalty_area_length: 1.65,
+                penalty_area_width: 4.0,
+            }),
+            ..standard_field_dimensions()
+        };
+
+        assert!(field_dimensions.is_inside_any_goal_box(point![-3.7, 1.3]));
+        assert!(!field_dimensions.is_inside_any_goal_box(point![3.7, 1.3]));
+        assert!(field_dimensions.is_inside_any_goal_box(point![4.0, 1.0]));
+        assert!(!field_dimensions.is_inside_any_goal_box(point![-3.7, 1.6]));
+    }
+
+    #[test]
+    fn box_corners_respect_half_overrides() {
+        let field_dimensions = FieldDimensions {
+            own_half_overrides: Some(HalfDimensions {
+                goal_box_area_length: 0.5,
+                goal_box_area_width: 2.0,
+                penalty_area_length: 1.5,
+                penalty_area_width: 3.0,
+            }),
+            ..standard_field_dimensions()
+        };
+
+        assert_corners_eq(
+            field_dimensions.penalty_box_corners(FieldHalf::Own),
+            [
+                point![-4.5, 1.5],
+                point![-4.5, -1.5],
+                point![-3.0, 1.5],
+                point![-3.0, -1.5],
+            ],
+        );
+        assert_corners_eq(
+            field_dimensions.goal_box_corners(FieldHalf::Opponent),
+            standard_field_dimensions().goal_box_corners(FieldHalf::Opponent),
+        );
     }
 }
@@ -2,12 +2,20 @@ use nalgebra::{distance, point, vector, Point2, Vector2};
 use ordered_float::NotNan;
 use serde::{Deserialize, Serialize};
 
-use super::{FieldDimensions, Line, Line2};
+use super::{FieldDimensions, FieldHalf, Line, Line2};
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub enum FieldMark {
     Line { line: Line2, direction: Direction },
     Circle { center: Point2<f32>, radius: f32 },
+    Cross { center: Point2<f32> },
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum FieldMarkKind {
+    Line,
+    Circle,
+    Cross,
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
@@ -17,6 +25,14 @@ pub enum Direction {
 }
 
 impl FieldMark {
+    pub fn kind(&self) -> FieldMarkKind {
+        match self {
+            FieldMark::Line { .. } => FieldMarkKind::Line,
+            FieldMark::Circle { .. } => FieldMarkKind::Circle,
+            FieldMark::Cross { .. } => FieldMarkKind::Cross,
+        }
+    }
+
     pub fn to_correspondence_points(self, measured_line: Line2) -> Correspondences {
         match self {
             FieldMark::Line {
@@ -88,25 +104,41 @@ impl FieldMark {
                     correspondence_points: (correspondence_0, correspondence_1),
                     measured_direction,
                     reference_direction,
+                    direction_agreement: measured_direction.dot(&reference_direction).abs(),
+                    source: self.kind(),
                 }
             }
             FieldMark::Circle { center, radius } => {
-                let center_to_0 = measured_line.0 - center;
-                let center_to_1 = measured_line.1 - center;
+                let is_chord_inside_circle = distance(&measured_line.0, &center) < radius
+                    && distance(&measured_line.1, &center) < radius;
 
                 let correspondence_0_measured = measured_line.0;
-                let correspondence_0_reference = if center_to_0 == Vector2::zeros() {
-                    point![center.x + radius, center.y]
-                } else {
-                    center + center_to_0.normalize() * radius
-                };
-
                 let correspondence_1_measured = measured_line.1;
-                let correspondence_1_reference = if center_to_1 == Vector2::zeros() {
-                    point![center.x + radius, center.y]
-                } else {
-                    center + center_to_1.normalize() * radius
-                };
+                let (correspondence_0_reference, correspondence_1_reference) =
+                    if is_chord_inside_circle {
+                        // radial projection of a chord shrinks it towards the center, moving along
+                        // the perpendicular bisector keeps the endpoints at their tangential offset
+                        let bisector_direction = chord_bisector_direction(center, measured_line);
+                        (
+                            project_along_direction_onto_circle(
+                                measured_line.0,
+                                center,
+                                radius,
+                                bisector_direction,
+                            ),
+                            project_along_direction_onto_circle(
+                                measured_line.1,
+                                center,
+                                radius,
+                                bisector_direction,
+                            ),
+                        )
+                    } else {
+                        (
+                            project_radially_onto_circle(measured_line.0, center, radius),
+                            project_radially_onto_circle(measured_line.1, center, radius),
+                        )
+                    };
 
                 let measured_direction = (measured_line.0 - measured_line.1).normalize();
                 let center_vector =
@@ -127,17 +159,133 @@ impl FieldMark {
                     ),
                     measured_direction,
                     reference_direction,
+                    direction_agreement: measured_direction.dot(&reference_direction).abs(),
+                    source: self.kind(),
+                }
+            }
+            FieldMark::Cross { center } => {
+                // a cross has no orientation, only the measured center is moved onto the reference
+                let center_offset = center - measured_line.center();
+                let measured_direction = (measured_line.0 - measured_line.1).normalize();
+
+                Correspondences {
+                    correspondence_points: (
+                        CorrespondencePoints {
+                            measured: measured_line.0,
+                            reference: measured_line.0 + center_offset,
+                        },
+                        CorrespondencePoints {
+                            measured: measured_line.1,
+                            reference: measured_line.1 + center_offset,
+                        },
+                    ),
+                    measured_direction,
+                    reference_direction: measured_direction,
+                    direction_agreement: 1.0,
+                    source: self.kind(),
                 }
             }
         }
     }
 }
 
+/// Greedily assigns measured lines to field marks, pairs with smaller correspondence distances first
+///
+/// Each measured line and each field mark is used at most once. Returns tuples of measured line
+/// index, field mark index and the correspondences, ordered by measured line index.
+pub fn match_lines_to_marks(
+    measured_lines: &[Line2],
+    field_marks: &[FieldMark],
+) -> Vec<(usize, usize, Correspondences)> {
+    let mut candidates: Vec<_> = measured_lines
+        .iter()
+        .enumerate()
+        .flat_map(|(measured_line_index, measured_line)| {
+            field_marks
+                .iter()
+                .enumerate()
+                .filter_map(move |(field_mark_index, field_mark)| {
+                    let correspondences = field_mark.to_correspondence_points(*measured_line);
+                    let (correspondence_0, correspondence_1) =
+                        correspondences.correspondence_points;
+                    let cost = NotNan::new(
+                        distance(&correspondence_0.measured, &correspondence_0.reference)
+                            + distance(&correspondence_1.measured, &correspondence_1.reference),
+                    )
+                    .ok()?;
+                    Some((cost, measured_line_index, field_mark_index, correspondences))
+                })
+        })
+        .collect();
+    candidates.sort_by_key(|(cost, ..)| *cost);
+
+    let mut is_measured_line_matched = vec![false; measured_lines.len()];
+    let mut is_field_mark_claimed = vec![false; field_marks.len()];
+    let mut matches: Vec<_> = candidates
+        .into_iter()
+        .filter_map(
+            |(_cost, measured_line_index, field_mark_index, correspondences)| {
+                if is_measured_line_matched[measured_line_index]
+                    || is_field_mark_claimed[field_mark_index]
+                {
+                    return None;
+                }
+                is_measured_line_matched[measured_line_index] = true;
+                is_field_mark_claimed[field_mark_index] = true;
+                Some((measured_line_index, field_mark_index, correspondences))
+            },
+        )
+        .collect();
+    matches.sort_by_key(|(measured_line_index, ..)| *measured_line_index);
+    matches
+}
+
+fn project_radially_onto_circle(
+    point: Point2<f32>,
+    center: Point2<f32>,
+    radius: f32,
+) -> Point2<f32> {
+    let center_to_point = point - center;
+    if center_to_point == Vector2::zeros() {
+        point![center.x + radius, center.y]
+    } else {
+        center + center_to_point.normalize() * radius
+    }
+}
+
+fn chord_bisector_direction(center: Point2<f32>, chord: Line2) -> Vector2<f32> {
+    let center_to_chord = chord.center() - center;
+    if center_to_chord == Vector2::zeros() {
+        let chord_direction = chord.1 - chord.0;
+        vector![-chord_direction.y, chord_direction.x].normalize()
+    } else {
+        center_to_chord.normalize()
+    }
+}
+
+/// Moves a point inside the circle along the unit vector `direction` until it hits the circle
+fn project_along_direction_onto_circle(
+    point: Point2<f32>,
+    center: Point2<f32>,
+    radius: f32,
+    direction: Vector2<f32>,
+) -> Point2<f32> {
+    let center_to_point = point - center;
+    let offset_along_direction = center_to_point.dot(&direction);
+    let distance_to_circle = -offset_along_direction
+        + (offset_along_direction.powi(2) - center_to_point.norm_squared() + radius.powi(2)).sqrt();
+    point + direction * distance_to_circle
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct Correspondences {
     pub correspondence_points: (CorrespondencePoints, CorrespondencePoints),
     pub measured_direction: Vector2<f32>,
     pub reference_direction: Vector2<f32>,
+    /// Absolute cosine of the angle between measured and reference direction, 1.0 if parallel
+    pub direction_agreement: f32,
+    /// Kind of the field mark the reference points lie on
+    pub source: FieldMarkKind,
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
@@ -146,8 +294,14 @@ pub struct CorrespondencePoints {
     pub reference: Point2<f32>,
 }
 
-pub fn field_marks_from_field_dimensions(field_dimensions: &FieldDimensions) -> Vec<FieldMark> {
-    vec![
+/// Penalty markers are either modeled as two crossing lines each or as [`FieldMark::Cross`]
+pub fn field_marks_from_field_dimensions(
+    field_dimensions: &FieldDimensions,
+    penalty_markers_as_crosses: bool,
+) -> Vec<FieldMark> {
+    let own_half = field_dimensions.half(FieldHalf::Own);
+    let opponent_half = field_dimensions.half(FieldHalf::Opponent);
+    let mut field_marks = vec![
         FieldMark::Line {
             line: Line(
                 point![-field_dimensions.length / 2.0, field_dimensions.width / 2.0],
@@ -186,11 +340,11 @@ pub fn field_marks_from_field_dimensions(field_dimensions: &FieldDimensions) ->
             line: Line(
                 point![
                     -field_dimensions.length / 2.0,
-                    field_dimensions.penalty_area_width / 2.0
+                    own_half.penalty_area_width / 2.0
                 ],
                 point![
-                    -field_dimensions.length / 2.0 + field_dimensions.penalty_area_length,
-                    field_dimensions.penalty_area_width / 2.0
+                    -field_dimensions.length / 2.0 + own_half.penalty_area_length,
+                    own_half.penalty_area_width / 2.0
                 ],
             ),
             direction: Direction::PositiveX,
@@ -199,11 +353,11 @@ pub fn field_marks_from_field_dimensions(field_dimensions: &FieldDimensions) ->
             line: Line(
                 point![
                     -field_dimensions.length / 2.0,
-                    -field_dimensions.penalty_area_width / 2.0
+                    -own_half.penalty_area_width / 2.0
                 ],
                 point![
-                    -field_dimensions.length / 2.0 + field_dimensions.penalty_area_length,
-                    -field_dimensions.penalty_area_width / 2.0
+                    -field_dimensions.length / 2.0 + own_half.penalty_area_length,
+                    -own_half.penalty_area_width / 2.0
                 ],
             ),
             direction: Direction::PositiveX,
@@ -211,12 +365,12 @@ pub fn field_marks_from_field_dimensions(field_dimensions: &FieldDimensions) ->
         FieldMark::Line {
             line: Line(
                 point![
-                    -field_dimensions.length / 2.0 + field_dimensions.penalty_area_length,
-                    -field_dimensions.penalty_area_width / 2.0
+                    -field_dimensions.length / 2.0 + own_half.penalty_area_length,
+                    -own_half.penalty_area_width / 2.0
                 ],
                 point![
-                    -field_dimensions.length / 2.0 + field_dimensions.penalty_area_length,
-                    field_dimensions.penalty_area_width / 2.0
+                    -field_dimensions.length / 2.0 + own_half.penalty_area_length,
+                    own_half.penalty_area_width / 2.0
                 ],
             ),
             direction: Direction::PositiveY,
@@ -225,11 +379,11 @@ pub fn field_marks_from_field_dimensions(field_dimensions: &FieldDimensions) ->
             line: Line(
                 point![
                     -field_dimensions.length / 2.0,
-                    field_dimensions.goal_box_area_width / 2.0
+                    own_half.goal_box_area_width / 2.0
                 ],
                 point![
-                    -field_dimensions.length / 2.0 + field_dimensions.goal_box_area_length,
-                    field_dimensions.goal_box_area_width / 2.0
+                    -field_dimensions.length / 2.0 + own_half.goal_box_area_length,
+                    own_half.goal_box_area_width / 2.0
                 ],
             ),
             direction: Direction::PositiveX,
@@ -238,11 +392,11 @@ pub fn field_marks_from_field_dimensions(field_dimensions: &FieldDimensions) ->
             line: Line(
                 point![
                     -field_dimensions.length / 2.0,
-                    -field_dimensions.goal_box_area_width / 2.0
+                    -own_half.goal_box_area_width / 2.0
                 ],
                 point![
-                    -field_dimensions.length / 2.0 + field_dimensions.goal_box_area_length,
-                    -field_dimensions.goal_box_area_width / 2.0
+                    -field_dimensions.length / 2.0 + own_half.goal_box_area_length,
+                    -own_half.goal_box_area_width / 2.0
                 ],
             ),
             direction: Direction::PositiveX,
@@ -250,12 +404,12 @@ pub fn field_marks_from_field_dimensions(field_dimensions: &FieldDimensions) ->
         FieldMark::Line {
             line: Line(
                 point![
-                    -field_dimensions.length / 2.0 + field_dimensions.goal_box_area_length,
-                    -field_dimensions.goal_box_area_width / 2.0
+                    -field_dimensions.length / 2.0 + own_half.goal_box_area_length,
+                    -own_half.goal_box_area_width / 2.0
                 ],
                 point![
-                    -field_dimensions.length / 2.0 + field_dimensions.goal_box_area_length,
-                    field_dimensions.goal_box_area_width / 2.0
+                    -field_dimensions.length / 2.0 + own_half.goal_box_area_length,
+                    own_half.goal_box_area_width / 2.0
                 ],
             ),
             direction: Direction::PositiveY,
@@ -263,12 +417,12 @@ pub fn field_marks_from_field_dimensions(field_dimensions: &FieldDimensions) ->
         FieldMark::Line {
             line: Line(
                 point![
-                    field_dimensions.length / 2.0 - field_dimensions.penalty_area_length,
-                    field_dimensions.penalty_area_width / 2.0
+                    field_dimensions.length / 2.0 - opponent_half.penalty_area_length,
+                    opponent_half.penalty_area_width / 2.0
                 ],
                 point![
                     field_dimensions.length / 2.0,
-                    field_dimensions.penalty_area_width / 2.0
+                    opponent_half.penalty_area_width / 2.0
                 ],
             ),
             direction: Direction::PositiveX,
@@ -276,12 +430,12 @@ pub fn field_marks_from_field_dimensions(field_dimensions: &FieldDimensions) ->
         FieldMark::Line {
             line: Line(
                 point![
-                    field_dimensions.length / 2.0 - field_dimensions.penalty_area_length,
-                    -field_dimensions.penalty_area_width / 2.0
+                    field_dimensions.length / 2.0 - opponent_half.penalty_area_length,
+                    -opponent_half.penalty_area_width / 2.0
                 ],
                 point![
                     field_dimensions.length / 2.0,
-                    -field_dimensions.penalty_area_width / 2.0
+                    -opponent_half.penalty_area_width / 2.0
                 ],
             ),
             direction: Direction::PositiveX,
@@ -289,12 +443,12 @@ pub fn field_marks_from_field_dimensions(field_dimensions: &FieldDimensions) ->
         FieldMark::Line {
             line: Line(
                 point![
-                    field_dimensions.length / 2.0 - field_dimensions.penalty_area_length,
-                    -field_dimensions.penalty_area_width / 2.0
+                    field_dimensions.length / 2.0 - opponent_half.penalty_area_length,
+                    -opponent_half.penalty_area_width / 2.0
                 ],
                 point![
-                    field_dimensions.length / 2.0 - field_dimensions.penalty_area_length,
-                    field_dimensions.penalty_area_width / 2.0
+                    field_dimensions.length / 2.0 - opponent_half.penalty_area_length,
+                    opponent_half.penalty_area_width / 2.0
                 ],
             ),
             direction: Direction::PositiveY,
@@ -302,12 +456,12 @@ pub fn field_marks_from_field_dimensions(field_dimensions: &FieldDimensions) ->
         FieldMark::Line {
             line: Line(
                 point![
-                    field_dimensions.length / 2.0 - field_dimensions.goal_box_area_length,
-                    field_dimensions.goal_box_area_width / 2.0
+                    field_dimensions.length / 2.0 - opponent_half.goal_box_area_length,
+                    opponent_half.goal_box_area_width / 2.0
                 ],
                 point![
                     field_dimensions.length / 2.0,
-                    field_dimensions.goal_box_area_width / 2.0
+                    opponent_half.goal_box_area_width / 2.0
                 ],
             ),
             direction: Direction::PositiveX,
@@ -315,12 +469,12 @@ pub fn field_marks_from_field_dimensions(field_dimensions: &FieldDimensions) ->
         FieldMark::Line {
             line: Line(
                 point![
-                    field_dimensions.length / 2.0 - field_dimensions.goal_box_area_length,
-                    -field_dimensions.goal_box_area_width / 2.0
+                    field_dimensions.length / 2.0 - opponent_half.goal_box_area_length,
+                    -opponent_half.goal_box_area_width / 2.0
                 ],
                 point![
                     field_dimensions.length / 2.0,
-                    -field_dimensions.goal_box_area_width / 2.0
+                    -opponent_half.goal_box_area_width / 2.0
                 ],
             ),
             direction: Direction::PositiveX,
@@ -328,12 +482,12 @@ pub fn field_marks_from_field_dimensions(field_dimensions: &FieldDimensions) ->
         FieldMark::Line {
             line: Line(
                 point![
-                    field_dimensions.length / 2.0 - field_dimensions.goal_box_area_length,
-                    -field_dimensions.goal_box_area_width / 2.0
+                    field_dimensions.length / 2.0 - opponent_half.goal_box_area_length,
+                    -opponent_half.goal_box_area_width / 2.0
                 ],
                 point![
-                    field_dimensions.length / 2.0 - field_dimensions.goal_box_area_length,
-                    field_dimensions.goal_box_area_width / 2.0
+                    field_dimensions.length / 2.0 - opponent_half.goal_box_area_length,
+                    opponent_half.goal_box_area_width / 2.0
                 ],
             ),
             direction: Direction::PositiveY,
@@ -349,63 +503,378 @@ pub fn field_marks_from_field_dimensions(field_dimensions: &FieldDimensions) ->
             center: Point2::origin(),
             radius: field_dimensions.center_circle_diameter / 2.0,
         },
-        FieldMark::Line {
-            line: Line(
-                point![
-                    -field_dimensions.length / 2.0 + field_dimensions.penalty_marker_distance
-                        - field_dimensions.penalty_marker_size / 2.0,
-                    0.0
-                ],
-                point![
-                    -field_dimensions.length / 2.0
-                        + field_dimensions.penalty_marker_distance
-                        + field_dimensions.penalty_marker_size / 2.0,
-                    0.0
-                ],
-            ),
-            direction: Direction::PositiveX,
-        },
-        FieldMark::Line {
-            line: Line(
-                point![
-                    -field_dimensions.length / 2.0 + field_dimensions.penalty_marker_distance,
-                    -field_dimensions.penalty_marker_size / 2.0
-                ],
-                point![
+    ];
+    if penalty_markers_as_crosses {
+        field_marks.extend([
+            FieldMark::Cross {
+                center: point![
                     -field_dimensions.length / 2.0 + field_dimensions.penalty_marker_distance,
-                    field_dimensions.penalty_marker_size / 2.0
-                ],
-            ),
-            direction: Direction::PositiveY,
-        },
-        FieldMark::Line {
-            line: Line(
-                point![
-                    field_dimensions.length / 2.0
-                        - field_dimensions.penalty_marker_distance
-                        - field_dimensions.penalty_marker_size / 2.0,
                     0.0
                 ],
-                point![
-                    field_dimensions.length / 2.0 - field_dimensions.penalty_marker_distance
-                        + field_dimensions.penalty_marker_size / 2.0,
+            },
+            FieldMark::Cross {
+                center: point![
+                    field_dimensions.length / 2.0 - field_dimensions.penalty_marker_distance,
                     0.0
                 ],
-            ),
+            },
+        ]);
+    } else {
+        field_marks.extend([
+            FieldMark::Line {
+                line: Line(
+                    point![
+                        -field_dimensions.length / 2.0 + field_dimensions.penalty_marker_distance
+                            - field_dimensions.penalty_marker_size / 2.0,
+                        0.0
+                    ],
+                    point![
+                        -field_dimensions.length / 2.0
+                            + field_dimensions.penalty_marker_distance
+                            + field_dimensions.penalty_marker_size / 2.0,
+                        0.0
+                    ],
+                ),
+                direction: Direction::PositiveX,
+            },
+            FieldMark::Line {
+                line: Line(
+                    point![
+                        -field_dimensions.length / 2.0 + field_dimensions.penalty_marker_distance,
+                        -field_dimensions.penalty_marker_size / 2.0
+                    ],
+                    point![
+                        -field_dimensions.length / 2.0 + field_dimensions.penalty_marker_distance,
+                        field_dimensions.penalty_marker_size / 2.0
+                    ],
+                ),
+                direction: Direction::PositiveY,
+            },
+            FieldMark::Line {
+                line: Line(
+                    point![
+                        field_dimensions.length / 2.0
+                            - field_dimensions.penalty_marker_distance
+                            - field_dimensions.penalty_marker_size / 2.0,
+                        0.0
+                    ],
+                    point![
+                        field_dimensions.length / 2.0 - field_dimensions.penalty_marker_distance
+                            + field_dimensions.penalty_marker_size / 2.0,
+                        0.0
+                    ],
+                ),
+                direction: Direction::PositiveX,
+            },
+            FieldMark::Line {
+                line: Line(
+                    point![
+                        field_dimensions.length / 2.0 - field_dimensions.penalty_marker_distance,
+                        -field_dimensions.penalty_marker_size / 2.0
+                    ],
+                    point![
+                        field_dimensions.length / 2.0 - field_dimensions.penalty_marker_distance,
+                        field_dimensions.penalty_marker_size / 2.0
+                    ],
+                ),
+                direction: Direction::PositiveY,
+            },
+        ]);
+    }
+    field_marks
+}
+
+/// Points where the halfway line crosses the center circle, ordered by increasing y
+pub fn center_circle_line_intersections(field_dimensions: &FieldDimensions) -> [Point2<f32>; 2] {
+    let radius = field_dimensions.center_circle_diameter / 2.0;
+    [point![0.0, -radius], point![0.0, radius]]
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use crate::HalfDimensions;
+
+    use super::*;
+
+    fn standard_field_dimensions() -> FieldDimensions {
+        FieldDimensions {
+            length: 9.0,
+            width: 6.0,
+            goal_box_area_length: 0.6,
+            goal_box_area_width: 2.2,
+            penalty_area_length: 1.65,
+            penalty_area_width: 4.0,
+            penalty_marker_distance: 1.3,
+            penalty_marker_size: 0.1,
+            center_circle_diameter: 1.5,
+            ..Default::default()
+        }
+    }
+
+    fn lines(field_marks: &[FieldMark]) -> Vec<Line2> {
+        field_marks
+            .iter()
+            .filter_map(|field_mark| match field_mark {
+                FieldMark::Line { line, .. } => Some(*line),
+                FieldMark::Circle { .. } | FieldMark::Cross { .. } => None,
+            })
+            .collect()
+    }
+
+    fn assert_lines_eq(actual: &[Line2], expected: &[Line2]) {
+        assert_eq!(actual.len(), expected.len());
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert_relative_eq!(actual.0, expected.0, epsilon = 1e-6);
+            assert_relative_eq!(actual.1, expected.1, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn overrides_matching_symmetric_dimensions_change_nothing() {
+        let symmetric = standard_field_dimensions();
+        let overridden = FieldDimensions {
+            own_half_overrides: Some(symmetric.half(FieldHalf::Own)),
+            opponent_half_overrides: Some(symmetric.half(FieldHalf::Opponent)),
+            ..symmetric.clone()
+        };
+
+        assert_lines_eq(
+            &lines(&field_marks_from_field_dimensions(&overridden, false)),
+            &lines(&field_marks_from_field_dimensions(&symmetric, false)),
+        );
+    }
+
+    #[test]
+    fn widened_own_penalty_area_only_moves_own_penalty_area_lines() {
+        let symmetric = standard_field_dimensions();
+        let asymmetric = FieldDimensions {
+            own_half_overrides: Some(HalfDimensions {
+                penalty_area_width: 5.0,
+                penalty_area_length: 2.0,
+                ..symmetric.half(FieldHalf::Own)
+            }),
+            ..symmetric.clone()
+        };
+
+        let symmetric_lines = lines(&field_marks_from_field_dimensions(&symmetric, false));
+        let asymmetric_lines = lines(&field_marks_from_field_dimensions(&asymmetric, false));
+
+        assert_lines_eq(
+            &asymmetric_lines[4..7],
+            &[
+                Line(point![-4.5, 2.5], point![-2.5, 2.5]),
+                Line(point![-4.5, -2.5], point![-2.5, -2.5]),
+                Line(point![-2.5, -2.5], point![-2.5, 2.5]),
+            ],
+        );
+        assert_lines_eq(&asymmetric_lines[..4], &symmetric_lines[..4]);
+        assert_lines_eq(&asymmetric_lines[7..], &symmetric_lines[7..]);
+        assert_lines_eq(
+            &asymmetric_lines[10..13],
+            &[
+                Line(point![2.85, 2.0], point![4.5, 2.0]),
+                Line(point![2.85, -2.0], point![4.5, -2.0]),
+                Line(point![2.85, -2.0], point![2.85, 2.0]),
+            ],
+        );
+    }
+
+    #[test]
+    fn center_circle_line_intersections_lie_on_circle_and_halfway_line() {
+        let field_dimensions = standard_field_dimensions();
+        let intersections = center_circle_line_intersections(&field_dimensions);
+
+        assert_relative_eq!(intersections[0], point![0.0, -0.75]);
+        assert_relative_eq!(intersections[1], point![0.0, 0.75]);
+
+        let halfway_line = lines(&field_marks_from_field_dimensions(&field_dimensions, false))[16];
+        for intersection in intersections {
+            assert_relative_eq!(
+                distance(&intersection, &Point2::origin()),
+                field_dimensions.center_circle_diameter / 2.0
+            );
+            assert_relative_eq!(halfway_line.distance_to_point(intersection), 0.0);
+        }
+    }
+
+    #[test]
+    fn shallow_chord_correspondences_keep_tangential_offset() {
+        let center = point![1.0, 2.0];
+        let radius = 1.0;
+        let circle = FieldMark::Circle { center, radius };
+        let measured_line = Line(point![0.4, 2.7], point![1.6, 2.7]);
+
+        let correspondences = circle.to_correspondence_points(measured_line);
+        let (correspondence_0, correspondence_1) = correspondences.correspondence_points;
+
+        assert_relative_eq!(correspondence_0.measured, measured_line.0);
+        assert_relative_eq!(correspondence_1.measured, measured_line.1);
+        assert_relative_eq!(correspondence_0.reference, point![0.4, 2.8], epsilon = 1e-6);
+        assert_relative_eq!(correspondence_1.reference, point![1.6, 2.8], epsilon = 1e-6);
+
+        let radially_projected_0 = project_radially_onto_circle(measured_line.0, center, radius);
+        let radially_projected_1 = project_radially_onto_circle(measured_line.1, center, radius);
+        let radial_error = (radially_projected_1 - radially_projected_0).norm()
+            - (measured_line.1 - measured_line.0).norm();
+        let bisector_error = (correspondence_1.reference - correspondence_0.reference).norm()
+            - (measured_line.1 - measured_line.0).norm();
+        assert!(bisector_error.abs() < radial_error.abs());
+    }
+
+    #[test]
+    fn segment_leaving_circle_is_projected_radially() {
+        let circle = FieldMark::Circle {
+            center: Point2::origin(),
+            radius: 1.0,
+        };
+        let measured_line = Line(point![0.0, 0.5], point![2.0, 0.0]);
+
+        let (correspondence_0, correspondence_1) = circle
+            .to_correspondence_points(measured_line)
+            .correspondence_points;
+
+        assert_relative_eq!(correspondence_0.reference, point![0.0, 1.0]);
+        assert_relative_eq!(correspondence_1.reference, point![1.0, 0.0]);
+    }
+
+    #[test]
+    fn measured_lines_are_matched_to_closest_field_marks() {
+        let field_marks = field_marks_from_field_dimensions(&standard_field_dimensions(), false);
+        let measured_lines = [
+            // halfway line
+            Line(point![0.05, -1.0], point![0.05, 1.0]),
+            // left penalty area front line
+            Line(point![-2.8, -1.5], point![-2.8, 1.5]),
+            // upper touchline
+            Line(point![1.0, 2.97], point![3.0, 2.97]),
+        ];
+
+        let matches = match_lines_to_marks(&measured_lines, &field_marks);
+
+        let indices: Vec<_> = matches
+            .iter()
+            .map(|(measured_line_index, field_mark_index, _)| {
+                (*measured_line_index, *field_mark_index)
+            })
+            .collect();
+        assert_eq!(indices, vec![(0, 16), (1, 6), (2, 0)]);
+    }
+
+    #[test]
+    fn claimed_field_marks_are_not_matched_twice() {
+        let field_marks = field_marks_from_field_dimensions(&standard_field_dimensions(), false);
+        let measured_lines = [
+            Line(point![0.1, -1.0], point![0.1, 1.0]),
+            Line(point![0.01, -1.0], point![0.01, 1.0]),
+        ];
+
+        let matches = match_lines_to_marks(&measured_lines, &field_marks);
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[1].1, 16);
+        assert_ne!(matches[0].1, 16);
+    }
+
+    #[test]
+    fn direction_agreement_of_aligned_and_perpendicular_lines() {
+        let field_mark = FieldMark::Line {
+            line: Line(point![-1.0, 0.0], point![1.0, 0.0]),
             direction: Direction::PositiveX,
-        },
-        FieldMark::Line {
-            line: Line(
-                point![
-                    field_dimensions.length / 2.0 - field_dimensions.penalty_marker_distance,
-                    -field_dimensions.penalty_marker_size / 2.0
-                ],
-                point![
-                    field_dimensions.length / 2.0 - field_dimensions.penalty_marker_distance,
-                    field_dimensions.penalty_marker_size / 2.0
-                ],
-            ),
-            direction: Direction::PositiveY,
-        },
-    ]
+        };
+
+        let aligned =
+            field_mark.to_correspondence_points(Line(point![0.5, 0.1], point![-0.5, 0.12]));
+        assert_relative_eq!(aligned.direction_agreement, 1.0, epsilon = 1e-3);
+
+        let perpendicular =
+            field_mark.to_correspondence_points(Line(point![0.0, 0.1], point![0.0, 1.1]));
+        assert_relative_eq!(perpendicular.direction_agreement, 0.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn direction_agreement_of_tangential_circle_segment() {
+        let field_mark = FieldMark::Circle {
+            center: Point2::origin(),
+            radius: 1.0,
+        };
+
+        let correspondences =
+            field_mark.to_correspondence_points(Line(point![-0.2, 0.98], point![0.2, 0.98]));
+        assert_relative_eq!(correspondences.direction_agreement, 1.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn correspondences_are_tagged_with_their_field_mark_kind() {
+        let measured_line = Line(point![0.5, 0.9], point![-0.5, 0.95]);
+        let line = FieldMark::Line {
+            line: Line(point![-1.0, 1.0], point![1.0, 1.0]),
+            direction: Direction::PositiveX,
+        };
+        let circle = FieldMark::Circle {
+            center: Point2::origin(),
+            radius: 1.0,
+        };
+        let cross = FieldMark::Cross {
+            center: point![0.0, 1.0],
+        };
+
+        assert_eq!(
+            line.to_correspondence_points(measured_line).source,
+            FieldMarkKind::Line
+        );
+        assert_eq!(
+            circle.to_correspondence_points(measured_line).source,
+            FieldMarkKind::Circle
+        );
+        assert_eq!(
+            cross.to_correspondence_points(measured_line).source,
+            FieldMarkKind::Cross
+        );
+    }
+
+    #[test]
+    fn penalty_markers_are_emitted_as_crosses_when_requested() {
+        let field_dimensions = standard_field_dimensions();
+        let with_lines = field_marks_from_field_dimensions(&field_dimensions, false);
+        let with_crosses = field_marks_from_field_dimensions(&field_dimensions, true);
+
+        assert_eq!(with_crosses.len(), with_lines.len() - 2);
+        let crosses: Vec<_> = with_crosses
+            .iter()
+            .filter_map(|field_mark| match field_mark {
+                FieldMark::Cross { center } => Some(*center),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(crosses.len(), 2);
+        assert_relative_eq!(crosses[0], point![-3.2, 0.0], epsilon = 1e-6);
+        assert_relative_eq!(crosses[1], point![3.2, 0.0], epsilon = 1e-6);
+    }
+
+    #[test]
+    fn measured_cross_center_corresponds_to_reference_center() {
+        let cross = FieldMark::Cross {
+            center: point![3.2, 0.0],
+        };
+        let measured_line = Line(point![3.0, 0.1], point![3.1, 0.2]);
+
+        let correspondences = cross.to_correspondence_points(measured_line);
+        let (correspondence_0, correspondence_1) = correspondences.correspondence_points;
+
+        assert_relative_eq!(correspondence_0.measured, measured_line.0);
+        assert_relative_eq!(correspondence_1.measured, measured_line.1);
+        assert_relative_eq!(
+            Line(correspondence_0.reference, correspondence_1.reference).center(),
+            point![3.2, 0.0],
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            correspondence_0.reference - correspondence_0.measured,
+            vector![0.15, -0.15],
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(correspondences.direction_agreement, 1.0);
+    }
 }
@@ -1,9 +1,14 @@
 use approx::{AbsDiffEq, RelativeEq};
-use nalgebra::{distance, vector, Point2, UnitComplex, Vector2};
+use nalgebra::{distance, vector, Matrix3, Point2, UnitComplex, Vector2, Vector3};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serialize_hierarchy::{Error, SerializeHierarchy};
 
-use std::{collections::BTreeSet, f32::consts::PI};
+use std::{
+    collections::BTreeSet,
+    f32::consts::{PI, TAU},
+};
+
+use crate::Line2;
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum Orientation {
@@ -27,6 +32,100 @@ pub fn rotate_towards(origin: Point2<f32>, target: Point2<f32>) -> UnitComplex<f
     UnitComplex::rotation_between(&Vector2::x(), &origin_to_target)
 }
 
+/// Groups lines into clusters of indices by comparing each line with the first line of a cluster
+///
+/// A line joins a cluster if the absolute cosine between both directions is at least
+/// `cosine_similarity` and both of its end points are within `maximum_distance` of the first line.
+pub fn cluster_lines(
+    lines: &[Line2],
+    maximum_distance: f32,
+    cosine_similarity: f32,
+) -> Vec<Vec<usize>> {
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let direction = (line.1 - line.0).normalize();
+        let matching_cluster = clusters.iter_mut().find(|cluster| {
+            let representative = lines[cluster[0]];
+            let representative_direction = (representative.1 - representative.0).normalize();
+            direction.dot(&representative_direction).abs() >= cosine_similarity
+                && representative.distance_to_point(line.0) <= maximum_distance
+                && representative.distance_to_point(line.1) <= maximum_distance
+        });
+        match matching_cluster {
+            Some(cluster) => cluster.push(index),
+            None => clusters.push(vec![index]),
+        }
+    }
+    clusters
+}
+
+/// Fraction of `bin_count` equally sized angular bins around `center` containing at least one point
+pub fn arc_coverage_ratio(center: Point2<f32>, points: &[Point2<f32>], bin_count: usize) -> f32 {
+    if bin_count == 0 {
+        return 0.0;
+    }
+    let is_bin_covered = covered_angular_bins(center, points, bin_count);
+    is_bin_covered
+        .iter()
+        .filter(|is_covered| **is_covered)
+        .count() as f32
+        / bin_count as f32
+}
+
+/// Arc coverage ratio of each quadrant around `center`, split into `bins_per_quadrant` bins
+///
+/// Quadrants are ordered counterclockwise starting at the negative x axis.
+pub fn quadrant_coverage_ratios(
+    center: Point2<f32>,
+    points: &[Point2<f32>],
+    bins_per_quadrant: usize,
+) -> [f32; 4] {
+    let mut ratios = [0.0; 4];
+    if bins_per_quadrant == 0 {
+        return ratios;
+    }
+    let bin_count = 4 * bins_per_quadrant;
+    let is_bin_covered = covered_angular_bins(center, points, bin_count);
+    for (ratio, bins) in ratios
+        .iter_mut()
+        .zip(is_bin_covered.chunks(bins_per_quadrant))
+    {
+        *ratio =
+            bins.iter().filter(|is_covered| **is_covered).count() as f32 / bins_per_quadrant as f32;
+    }
+    ratios
+}
+
+/// Whether at least `minimum_number_of_quadrants` quadrants reach `minimum_quadrant_coverage`
+pub fn is_arc_spanning_quadrants(
+    center: Point2<f32>,
+    points: &[Point2<f32>],
+    bins_per_quadrant: usize,
+    minimum_quadrant_coverage: f32,
+    minimum_number_of_quadrants: usize,
+) -> bool {
+    quadrant_coverage_ratios(center, points, bins_per_quadrant)
+        .iter()
+        .filter(|ratio| **ratio >= minimum_quadrant_coverage)
+        .count()
+        >= minimum_number_of_quadrants
+}
+
+fn covered_angular_bins(
+    center: Point2<f32>,
+    points: &[Point2<f32>],
+    bin_count: usize,
+) -> Vec<bool> {
+    let mut is_bin_covered = vec![false; bin_count];
+    for point in points {
+        let center_to_point = point - center;
+        let angle = center_to_point.y.atan2(center_to_point.x) + PI;
+        let bin = ((angle / TAU * bin_count as f32) as usize).min(bin_count - 1);
+        is_bin_covered[bin] = true;
+    }
+    is_bin_covered
+}
+
 #[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct LineSegment(pub Point2<f32>, pub Point2<f32>);
 
@@ -82,11 +181,16 @@ impl LineSegment {
     }
 
     pub fn closest_point(&self, point: Point2<f32>) -> Point2<f32> {
+        if self.norm_squared() == 0.0 {
+            return self.0;
+        }
         let projected_factor = self.projection_factor(point).clamp(0.0, 1.0);
         self.0 + projected_factor * (self.1 - self.0)
     }
 
-    pub fn shortest_distance_to_point(&self, other_point: Point2<f32>) -> f32 {
+    /// Distance to the closest point on the segment, i.e. the distance to the nearer end point for
+    /// points beyond either end
+    pub fn distance_to_point(&self, other_point: Point2<f32>) -> f32 {
         (other_point - self.closest_point(other_point)).norm()
     }
 
@@ -141,7 +245,7 @@ impl LineSegment {
     }
 
     pub fn overlaps_arc(&self, arc: Arc, orientation: Orientation) -> bool {
-        if self.shortest_distance_to_point(arc.circle.center) >= arc.circle.radius {
+        if self.distance_to_point(arc.circle.center) >= arc.circle.radius {
             return false;
         }
 
@@ -405,10 +509,66 @@ impl Circle {
         Self { center, radius }
     }
 
+    /// Circumscribed circle of the triangle `a`, `b`, `c`, `None` for (nearly) collinear points
+    pub fn from_three_points(a: Point2<f32>, b: Point2<f32>, c: Point2<f32>) -> Option<Self> {
+        let a_to_b = b - a;
+        let a_to_c = c - a;
+        let twice_signed_area = a_to_b.perp(&a_to_c);
+        if twice_signed_area.abs() <= f32::EPSILON * a_to_b.norm() * a_to_c.norm() {
+            return None;
+        }
+        let center_offset = vector![
+            a_to_c.y * a_to_b.norm_squared() - a_to_b.y * a_to_c.norm_squared(),
+            a_to_b.x * a_to_c.norm_squared() - a_to_c.x * a_to_b.norm_squared()
+        ] / (2.0 * twice_signed_area);
+        Some(Self::new(a + center_offset, center_offset.norm()))
+    }
+
+    /// Algebraic least squares fit (Kåsa method) to at least three points
+    ///
+    /// Returns `None` for fewer than three points or if they do not determine a circle, e.g. when
+    /// all of them are collinear.
+    pub fn from_points(points: &[Point2<f32>]) -> Option<Self> {
+        if points.len() < 3 {
+            return None;
+        }
+        // centering the points keeps the normal equations well conditioned
+        let mean = points
+            .iter()
+            .map(|point| point.coords)
+            .sum::<Vector2<f32>>()
+            / points.len() as f32;
+        let mut normal_matrix = Matrix3::zeros();
+        let mut right_hand_side = Vector3::zeros();
+        for point in points {
+            let centered = point.coords - mean;
+            let row = vector![centered.x, centered.y, 1.0];
+            normal_matrix += row * row.transpose();
+            right_hand_side -= row * centered.norm_squared();
+        }
+        let solution = normal_matrix.lu().solve(&right_hand_side)?;
+        let center_offset = vector![-solution.x / 2.0, -solution.y / 2.0];
+        let radius_squared = center_offset.norm_squared() - solution.z;
+        (radius_squared.is_finite() && radius_squared > 0.0)
+            .then(|| Self::new(Point2::from(mean + center_offset), radius_squared.sqrt()))
+    }
+
     pub fn contains(&self, point: Point2<f32>) -> bool {
         distance(&self.center, &point) <= self.radius
     }
 
+    /// Root mean square of the radial distances of `points` to the circle, zero without points
+    pub fn rms_residual(&self, points: &[Point2<f32>]) -> f32 {
+        if points.is_empty() {
+            return 0.0;
+        }
+        let sum_of_squared_residuals: f32 = points
+            .iter()
+            .map(|point| (distance(&self.center, point) - self.radius).powi(2))
+            .sum();
+        (sum_of_squared_residuals / points.len() as f32).sqrt()
+    }
+
     pub fn bounding_box(&self) -> Rectangle {
         let radius_vector = vector![self.radius, self.radius];
 
@@ -419,7 +579,7 @@ impl Circle {
     }
 
     pub fn intersects_line_segment(&self, line_segment: &LineSegment) -> bool {
-        line_segment.shortest_distance_to_point(self.center) <= self.radius
+        line_segment.distance_to_point(self.center) <= self.radius
     }
 
     pub fn overlaps_arc(&self, arc: Arc, orientation: Orientation) -> bool {
@@ -583,15 +743,39 @@ impl Rectangle {
         let dimensions = self.max - self.min;
         dimensions.x * dimensions.y
     }
+
+    /// Points on the border are contained
+    pub fn contains(self, point: Point2<f32>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// Returns `None` if the rectangles do not overlap, touching rectangles intersect in a border
+    pub fn intersection(self, other: Rectangle) -> Option<Rectangle> {
+        let min = self.min.sup(&other.min);
+        let max = self.max.inf(&other.max);
+        (min.x <= max.x && min.y <= max.y).then_some(Rectangle { min, max })
+    }
+
+    /// Grows each side by `padding`, negative padding shrinks down to the center at most
+    pub fn expanded(self, padding: f32) -> Rectangle {
+        let center = nalgebra::center(&self.min, &self.max);
+        let padding = vector![padding, padding];
+        Rectangle {
+            min: (self.min - padding).inf(&center),
+            max: (self.max + padding).sup(&center),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::f32::consts::PI;
+    use std::{f32::consts::PI, ops::Range};
 
     use approx::{assert_relative_eq, assert_relative_ne};
     use nalgebra::{point, Point2, UnitComplex};
 
+    use crate::Line;
+
     use super::*;
 
     #[test]
@@ -692,6 +876,182 @@ mod tests {
         );
     }
 
+    #[test]
+    fn circle_from_three_points_on_circle() {
+        let circle = Circle::new(point![3.0, -1.5], 0.75);
+        let [a, b, c] = [10.0_f32, 130.0, 250.0].map(|degrees| {
+            let angle = degrees.to_radians();
+            circle.center + vector![angle.cos(), angle.sin()] * circle.radius
+        });
+
+        let fitted = Circle::from_three_points(a, b, c).expect("No circle was found");
+
+        assert_relative_eq!(fitted, circle, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn circle_from_three_points_with_shared_y_coordinate() {
+        let fitted =
+            Circle::from_three_points(point![0.0, 0.0], point![2.0, 0.0], point![1.0, 1.0])
+                .expect("No circle was found");
+
+        assert_relative_eq!(fitted, Circle::new(point![1.0, 0.0], 1.0), epsilon = 1e-6);
+    }
+
+    #[test]
+    fn no_circle_from_degenerate_three_points() {
+        assert_eq!(
+            Circle::from_three_points(point![0.0, 0.0], point![1.0, 1.0], point![3.0, 3.0]),
+            None
+        );
+        assert_eq!(
+            Circle::from_three_points(point![0.0, 2.0], point![1.0, 2.0], point![-4.0, 2.0]),
+            None
+        );
+        assert_eq!(
+            Circle::from_three_points(point![1.0, 2.0], point![1.0, 2.0], point![-4.0, 0.0]),
+            None
+        );
+    }
+
+    #[test]
+    fn circle_from_noisy_points() {
+        let circle = Circle::new(point![3.0, -1.5], 0.75);
+        let points: Vec<_> = (0..100)
+            .map(|index| {
+                let angle = index as f32 * 3.6_f32.to_radians();
+                let noise = ((index * 37) % 21) as f32 / 1000.0 - 0.01;
+                let radius = circle.radius + noise;
+                circle.center + vector![angle.cos(), angle.sin()] * radius
+            })
+            .collect();
+
+        let fitted = Circle::from_points(&points).expect("No circle was fitted");
+
+        assert_relative_eq!(fitted.center, circle.center, epsilon = 0.005);
+        assert_relative_eq!(fitted.radius, circle.radius, epsilon = 0.005);
+    }
+
+    #[test]
+    fn no_circle_from_too_few_or_collinear_points() {
+        assert_eq!(
+            Circle::from_points(&[point![0.0, 0.0], point![1.0, 1.0]]),
+            None
+        );
+        assert_eq!(
+            Circle::from_points(&[point![0.0, 0.0], point![1.0, 1.0], point![2.0, 2.0]]),
+            None
+        );
+    }
+
+    #[test]
+    fn rms_residual_of_points_on_circle() {
+        let circle = Circle::new(point![1.0, -2.0], 0.75);
+        let points: Vec<_> = (0..36)
+            .map(|index| {
+                let angle = index as f32 * 10.0_f32.to_radians();
+                circle.center + vector![angle.cos(), angle.sin()] * circle.radius
+            })
+            .collect();
+
+        assert_relative_eq!(circle.rms_residual(&points), 0.0, epsilon = 1e-6);
+        assert_relative_eq!(circle.rms_residual(&[]), 0.0);
+    }
+
+    #[test]
+    fn rms_residual_of_noisy_points() {
+        let circle = Circle::new(point![1.0, -2.0], 0.75);
+        let points: Vec<_> = (0..36)
+            .map(|index| {
+                let angle = index as f32 * 10.0_f32.to_radians();
+                let noise = if index % 2 == 0 { 0.02 } else { -0.02 };
+                circle.center + vector![angle.cos(), angle.sin()] * (circle.radius + noise)
+            })
+            .collect();
+
+        assert_relative_eq!(circle.rms_residual(&points), 0.02, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn rectangle_contains_points_on_border() {
+        let rectangle = Rectangle {
+            min: point![-1.0, 2.0],
+            max: point![3.0, 4.0],
+        };
+
+        assert!(rectangle.contains(point![0.0, 3.0]));
+        assert!(rectangle.contains(point![-1.0, 2.0]));
+        assert!(rectangle.contains(point![3.0, 3.0]));
+        assert!(!rectangle.contains(point![3.1, 3.0]));
+        assert!(!rectangle.contains(point![0.0, 1.9]));
+    }
+
+    #[test]
+    fn rectangle_intersection_of_overlapping_and_separate_rectangles() {
+        let rectangle = Rectangle {
+            min: point![0.0, 0.0],
+            max: point![2.0, 2.0],
+        };
+
+        assert_eq!(
+            rectangle.intersection(Rectangle {
+                min: point![1.0, -1.0],
+                max: point![3.0, 1.5],
+            }),
+            Some(Rectangle {
+                min: point![1.0, 0.0],
+                max: point![2.0, 1.5],
+            })
+        );
+        assert_eq!(
+            rectangle.intersection(Rectangle {
+                min: point![2.5, 0.0],
+                max: point![3.0, 2.0],
+            }),
+            None
+        );
+        assert_eq!(
+            rectangle.intersection(Rectangle {
+                min: point![2.0, 0.0],
+                max: point![3.0, 2.0],
+            }),
+            Some(Rectangle {
+                min: point![2.0, 0.0],
+                max: point![2.0, 2.0],
+            })
+        );
+    }
+
+    #[test]
+    fn rectangle_expanded_by_positive_and_negative_padding() {
+        let rectangle = Rectangle {
+            min: point![0.0, 0.0],
+            max: point![4.0, 2.0],
+        };
+
+        assert_eq!(
+            rectangle.expanded(1.0),
+            Rectangle {
+                min: point![-1.0, -1.0],
+                max: point![5.0, 3.0],
+            }
+        );
+        assert_eq!(
+            rectangle.expanded(-0.5),
+            Rectangle {
+                min: point![0.5, 0.5],
+                max: point![3.5, 1.5],
+            }
+        );
+        assert_eq!(
+            rectangle.expanded(-1.5),
+            Rectangle {
+                min: point![1.5, 1.0],
+                max: point![2.5, 1.0],
+            }
+        );
+    }
+
     #[test]
     fn line_segment_lengths() {
         for i in 0..10 {
@@ -708,25 +1068,48 @@ mod tests {
     fn shortest_distance_between_point_and_line_segment() {
         let line_segment = LineSegment(point![-1.0, 0.0], point![1.0, 0.0]);
 
+        assert_relative_eq!(0.0, line_segment.distance_to_point(point![-1.0, 0.0]));
+        assert_relative_eq!(0.0, line_segment.distance_to_point(point![1.0, 0.0]));
+        assert_relative_eq!(1.0, line_segment.distance_to_point(point![0.0, 1.0]));
         assert_relative_eq!(
-            0.0,
-            line_segment.shortest_distance_to_point(point![-1.0, 0.0])
-        );
-        assert_relative_eq!(
-            0.0,
-            line_segment.shortest_distance_to_point(point![1.0, 0.0])
+            2.0_f32.sqrt(),
+            line_segment.distance_to_point(point![2.0, -1.0])
         );
+        assert_relative_eq!(0.5, line_segment.distance_to_point(point![-0.5, -0.5]));
+    }
+
+    #[test]
+    fn distance_beyond_line_segment_endpoints_is_endpoint_distance() {
+        let line_segment = LineSegment(point![1.0, 1.0], point![3.0, 1.0]);
+
+        assert_relative_eq!(line_segment.distance_to_point(point![-2.0, 5.0]), 5.0);
+        assert_relative_eq!(line_segment.distance_to_point(point![6.0, -3.0]), 5.0);
         assert_relative_eq!(
-            1.0,
-            line_segment.shortest_distance_to_point(point![0.0, 1.0])
+            line_segment.flip().distance_to_point(point![-2.0, 5.0]),
+            5.0
         );
         assert_relative_eq!(
-            2.0_f32.sqrt(),
-            line_segment.shortest_distance_to_point(point![2.0, -1.0])
+            line_segment.closest_point(point![6.0, -3.0]),
+            point![3.0, 1.0]
         );
+    }
+
+    #[test]
+    fn circle_beyond_line_segment_end_does_not_intersect_it() {
+        let line_segment = LineSegment(point![-1.0, 0.0], point![1.0, 0.0]);
+
+        assert!(!Circle::new(point![5.0, 0.0], 1.0).intersects_line_segment(&line_segment));
+        assert!(Circle::new(point![1.5, 0.0], 1.0).intersects_line_segment(&line_segment));
+    }
+
+    #[test]
+    fn distance_to_degenerate_line_segment_is_point_distance() {
+        let line_segment = LineSegment(point![1.0, 1.0], point![1.0, 1.0]);
+
+        assert_relative_eq!(line_segment.distance_to_point(point![4.0, 5.0]), 5.0);
         assert_relative_eq!(
-            0.5,
-            line_segment.shortest_distance_to_point(point![-0.5, -0.5])
+            line_segment.closest_point(point![4.0, 5.0]),
+            point![1.0, 1.0]
         );
     }
 
@@ -952,4 +1335,117 @@ mod tests {
         let line_segment = LineSegment(point![5.0, 4.0], point![4.0, 5.0]);
         test_all_permutations(reference_line_segment, line_segment, false);
     }
+
+    #[test]
+    fn near_parallel_close_lines_are_clustered() {
+        let lines = [
+            Line(point![0.0, 0.0], point![2.0, 0.0]),
+            Line(point![0.5, 0.05], point![2.5, 0.08]),
+            Line(point![3.0, -0.04], point![1.0, -0.02]),
+        ];
+        assert_eq!(cluster_lines(&lines, 0.1, 0.99), vec![vec![0, 1, 2]]);
+    }
+
+    #[test]
+    fn perpendicular_or_distant_lines_stay_separate() {
+        let lines = [
+            Line(point![0.0, 0.0], point![2.0, 0.0]),
+            Line(point![1.0, -1.0], point![1.0, 1.0]),
+            Line(point![0.0, 1.0], point![2.0, 1.0]),
+            Line(point![1.05, 0.5], point![1.0, -0.5]),
+        ];
+        assert_eq!(
+            cluster_lines(&lines, 0.1, 0.99),
+            vec![vec![0], vec![1, 3], vec![2]]
+        );
+    }
+
+    fn points_on_arc(center: Point2<f32>, radius: f32, degrees: Range<i32>) -> Vec<Point2<f32>> {
+        degrees
+            .map(|degree| {
+                let angle = (degree as f32).to_radians();
+                center + vector![angle.cos(), angle.sin()] * radius
+            })
+            .collect()
+    }
+
+    #[test]
+    fn arc_coverage_ratio_of_full_circle() {
+        let center = point![2.0, -1.0];
+        let points = points_on_arc(center, 0.75, 0..360);
+        assert_relative_eq!(arc_coverage_ratio(center, &points, 36), 1.0);
+        assert_relative_eq!(arc_coverage_ratio(center, &points, 66), 1.0);
+    }
+
+    #[test]
+    fn arc_coverage_ratio_of_half_circle() {
+        let center = point![2.0, -1.0];
+        let points = points_on_arc(center, 0.75, 0..180);
+        assert_relative_eq!(arc_coverage_ratio(center, &points, 36), 0.5);
+    }
+
+    #[test]
+    fn arc_coverage_ratio_depends_on_bin_count() {
+        let center = Point2::origin();
+        let points = points_on_arc(center, 1.0, 0..90)
+            .into_iter()
+            .step_by(30)
+            .collect::<Vec<_>>();
+        assert_relative_eq!(arc_coverage_ratio(center, &points, 4), 0.25);
+        assert_relative_eq!(arc_coverage_ratio(center, &points, 36), 3.0 / 36.0);
+    }
+
+    #[test]
+    fn arc_coverage_ratio_without_points_or_bins() {
+        assert_relative_eq!(arc_coverage_ratio(Point2::origin(), &[], 36), 0.0);
+        assert_relative_eq!(
+            arc_coverage_ratio(Point2::origin(), &[point![1.0, 0.0]], 0),
+            0.0
+        );
+    }
+
+    #[test]
+    fn quadrant_coverage_ratios_of_three_quarter_circle() {
+        let center = point![2.0, -1.0];
+        let points = points_on_arc(center, 0.75, 0..270);
+        let ratios = quadrant_coverage_ratios(center, &points, 9);
+        // 0..90 degrees lies in the third quadrant counted from the negative x axis
+        assert_relative_eq!(ratios[0], 1.0);
+        assert_relative_eq!(ratios[1], 0.0);
+        assert_relative_eq!(ratios[2], 1.0);
+        assert_relative_eq!(ratios[3], 1.0);
+    }
+
+    #[test]
+    fn arc_spanning_quadrants() {
+        let center = point![2.0, -1.0];
+        let three_quadrant_arc = points_on_arc(center, 0.75, 0..270);
+        let dense_single_quadrant_arc = points_on_arc(center, 0.75, 0..90)
+            .into_iter()
+            .cycle()
+            .take(1000)
+            .collect::<Vec<_>>();
+
+        assert!(is_arc_spanning_quadrants(
+            center,
+            &three_quadrant_arc,
+            9,
+            0.5,
+            3
+        ));
+        assert!(!is_arc_spanning_quadrants(
+            center,
+            &dense_single_quadrant_arc,
+            9,
+            0.5,
+            3
+        ));
+        assert!(!is_arc_spanning_quadrants(
+            center,
+            &three_quadrant_arc,
+            9,
+            0.5,
+            4
+        ));
+    }
 }
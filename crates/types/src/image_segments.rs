@@ -11,6 +11,7 @@ pub struct ImageSegments {
 #[derive(Default, Clone, Debug, Deserialize, Serialize, SerializeHierarchy)]
 pub struct ScanGrid {
     pub vertical_scan_lines: Vec<ScanLine>,
+    pub horizontal_scan_lines: Vec<ScanLine>,
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
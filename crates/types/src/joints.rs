@@ -544,7 +544,7 @@ where
             self.left_arm.as_vec(),
             self.right_arm.as_vec(),
             self.left_leg.as_vec(),
-            self.right_arm.as_vec(),
+            self.right_leg.as_vec(),
         ]
     }
 }
@@ -738,3 +738,83 @@ pub struct BodyJointsCommand<T> {
     pub positions: BodyJoints<T>,
     pub stiffnesses: BodyJoints<T>,
 }
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    fn distinct_joints() -> Joints<f32> {
+        let mut angles = [0.0; 26];
+        for (index, angle) in angles.iter_mut().enumerate() {
+            *angle = 0.1 + index as f32 * 0.05;
+        }
+        Joints::from_angles(angles)
+    }
+
+    fn assert_mirror_roundtrip(joints: Joints<f32>) {
+        let roundtrip = joints.mirrored().mirrored();
+        for (expected, actual) in joints
+            .as_vec()
+            .into_iter()
+            .flatten()
+            .zip(roundtrip.as_vec().into_iter().flatten())
+        {
+            assert_relative_eq!(expected, actual, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn mirroring_twice_is_identity() {
+        assert_mirror_roundtrip(distinct_joints());
+        assert_mirror_roundtrip(Joints::fill(-0.3));
+        assert_mirror_roundtrip(Joints::default());
+    }
+
+    #[test]
+    fn mirroring_swaps_sides_and_negates_lateral_joints() {
+        let joints = distinct_joints();
+
+        let mirrored = joints.mirrored();
+
+        assert_eq!(mirrored.head.yaw, -joints.head.yaw);
+        assert_eq!(mirrored.head.pitch, joints.head.pitch);
+
+        assert_eq!(
+            mirrored.left_arm.shoulder_pitch,
+            joints.right_arm.shoulder_pitch
+        );
+        assert_eq!(
+            mirrored.left_arm.shoulder_roll,
+            -joints.right_arm.shoulder_roll
+        );
+        assert_eq!(mirrored.left_arm.elbow_yaw, -joints.right_arm.elbow_yaw);
+        assert_eq!(mirrored.left_arm.elbow_roll, -joints.right_arm.elbow_roll);
+        assert_eq!(mirrored.left_arm.wrist_yaw, -joints.right_arm.wrist_yaw);
+        assert_eq!(mirrored.left_arm.hand, joints.right_arm.hand);
+        assert_eq!(mirrored.right_arm, joints.left_arm.mirrored());
+
+        assert_eq!(
+            mirrored.left_leg.hip_yaw_pitch,
+            joints.right_leg.hip_yaw_pitch
+        );
+        assert_eq!(mirrored.left_leg.hip_roll, -joints.right_leg.hip_roll);
+        assert_eq!(mirrored.left_leg.hip_pitch, joints.right_leg.hip_pitch);
+        assert_eq!(mirrored.left_leg.knee_pitch, joints.right_leg.knee_pitch);
+        assert_eq!(mirrored.left_leg.ankle_pitch, joints.right_leg.ankle_pitch);
+        assert_eq!(mirrored.left_leg.ankle_roll, -joints.right_leg.ankle_roll);
+        assert_eq!(mirrored.right_leg, joints.left_leg.mirrored());
+    }
+
+    #[test]
+    fn as_vec_contains_every_joint_once() {
+        let joints = distinct_joints();
+
+        let mut angles: Vec<_> = joints.as_vec().into_iter().flatten().collect();
+        angles.sort_by(f32::total_cmp);
+        angles.dedup();
+
+        assert_eq!(angles.len(), 26);
+    }
+}
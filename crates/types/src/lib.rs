@@ -85,15 +85,17 @@ pub use cycle_time::CycleTime;
 pub use fall_state::FallState;
 pub use field_border::FieldBorder;
 pub use field_color::FieldColor;
-pub use field_dimensions::FieldDimensions;
+pub use field_dimensions::{FieldDimensions, FieldHalf, HalfDimensions};
 pub use field_marks::{
-    field_marks_from_field_dimensions, CorrespondencePoints, Correspondences, Direction, FieldMark,
+    center_circle_line_intersections, field_marks_from_field_dimensions, match_lines_to_marks,
+    CorrespondencePoints, Correspondences, Direction, FieldMark, FieldMarkKind,
 };
 pub use filtered_game_state::FilteredGameState;
 pub use filtered_segments::FilteredSegments;
 pub use filtered_whistle::FilteredWhistle;
 pub use game_controller_state::GameControllerState;
 pub use geometry::{
+    arc_coverage_ratio, cluster_lines, is_arc_spanning_quadrants, quadrant_coverage_ratios,
     rotate_towards, Arc, Circle, LineSegment, Orientation, Rectangle, TwoLineSegments,
 };
 pub use image_segments::{EdgeType, ImageSegments, ScanGrid, ScanLine, Segment};
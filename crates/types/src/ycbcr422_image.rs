@@ -15,7 +15,7 @@ use nalgebra::Point2;
 use serde::{Deserialize, Serialize};
 use serialize_hierarchy::{DecodeJpeg, EncodeJpeg, SerializeHierarchy};
 
-use crate::{Rgb, YCbCr422, YCbCr444};
+use crate::{Rectangle, Rgb, YCbCr422, YCbCr444};
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize, SerializeHierarchy)]
 #[serialize_hierarchy(as_jpeg)]
@@ -135,10 +135,18 @@ impl YCbCr422Image {
     }
 
     pub fn load_from_rgb_file(path: impl AsRef<Path>) -> eyre::Result<Self> {
-        let png = Reader::open(path)?.decode()?.into_rgb8();
+        let path = path.as_ref();
+        let png = Reader::open(path)
+            .wrap_err_with(|| format!("failed to open image {path:?}"))?
+            .decode()
+            .wrap_err_with(|| format!("failed to decode image {path:?}"))?
+            .into_rgb8();
 
         let width = png.width();
         let height = png.height();
+        if width % 2 != 0 {
+            eyre::bail!("image {path:?} has odd width {width}, which YCbCr422 does not support");
+        }
 
         let pixels = buffer_422_from_rgb_image(png);
 
@@ -187,6 +195,58 @@ impl YCbCr422Image {
         };
         Some(pixel)
     }
+
+    /// Copies the pixels within `region` (maximum exclusive) into a new image
+    ///
+    /// The region is clamped to the image. Its horizontal bounds are widened to even columns
+    /// because two neighbouring pixels share their chroma values.
+    pub fn crop(&self, region: Rectangle) -> Self {
+        let minimum_x_422 = (region.min.x.max(0.0) as u32 / 2).min(self.width_422);
+        let maximum_x_422 = ((region.max.x.max(0.0).ceil() as u32 + 1) / 2).min(self.width_422);
+        let minimum_y = (region.min.y.max(0.0) as u32).min(self.height);
+        let maximum_y = (region.max.y.max(0.0).ceil() as u32).min(self.height);
+        let width_422 = maximum_x_422.saturating_sub(minimum_x_422);
+        let buffer = (minimum_y..maximum_y)
+            .flat_map(|y| {
+                let row_start = (y * self.width_422 + minimum_x_422) as usize;
+                self.buffer[row_start..row_start + width_422 as usize]
+                    .iter()
+                    .copied()
+            })
+            .collect();
+        Self::from_ycbcr_buffer(width_422, maximum_y.saturating_sub(minimum_y), buffer)
+    }
+
+    /// Halves the resolution by averaging 2x2 pixel blocks
+    ///
+    /// Each output 422 pixel covers two neighbouring 422 pixels in two consecutive rows. Its
+    /// chroma is the average of the four covered chroma values. Trailing rows and columns which
+    /// do not fill a complete block are dropped.
+    pub fn downsample_2x(&self) -> Self {
+        let width_422 = self.width_422 / 2;
+        let height = self.height / 2;
+        let buffer = (0..height)
+            .flat_map(|y| {
+                (0..width_422).map(move |x| {
+                    let top_row = (2 * y * self.width_422 + 2 * x) as usize;
+                    let bottom_row = top_row + self.width_422 as usize;
+                    let [top_left, top_right] = [self.buffer[top_row], self.buffer[top_row + 1]];
+                    let [bottom_left, bottom_right] =
+                        [self.buffer[bottom_row], self.buffer[bottom_row + 1]];
+                    let average = |values: [u8; 4]| {
+                        (values.iter().map(|&value| value as u16).sum::<u16>() / 4) as u8
+                    };
+                    YCbCr422 {
+                        y1: average([top_left.y1, top_left.y2, bottom_left.y1, bottom_left.y2]),
+                        cb: average([top_left.cb, top_right.cb, bottom_left.cb, bottom_right.cb]),
+                        y2: average([top_right.y1, top_right.y2, bottom_right.y1, bottom_right.y2]),
+                        cr: average([top_left.cr, top_right.cr, bottom_left.cr, bottom_right.cr]),
+                    }
+                })
+            })
+            .collect();
+        Self::from_ycbcr_buffer(width_422, height, buffer)
+    }
 }
 
 impl Index<Point2<usize>> for YCbCr422Image {
@@ -252,3 +312,100 @@ fn buffer_422_from_rgb_image(rgb_image: RgbImage) -> Vec<YCbCr422> {
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::point;
+
+    use super::*;
+
+    fn numbered_image(width_422: u32, height: u32) -> YCbCr422Image {
+        let buffer = (0..height)
+            .flat_map(|y| {
+                (0..width_422).map(move |x| {
+                    let y1 = (10 * y + 2 * x) as u8;
+                    YCbCr422::new(y1, x as u8, y1 + 1, y as u8)
+                })
+            })
+            .collect();
+        YCbCr422Image::from_ycbcr_buffer(width_422, height, buffer)
+    }
+
+    #[test]
+    fn crop_returns_sub_pixels() {
+        let image = numbered_image(4, 3);
+
+        let cropped = image.crop(Rectangle {
+            min: point![2.0, 1.0],
+            max: point![6.0, 3.0],
+        });
+
+        assert_eq!(cropped.width(), 4);
+        assert_eq!(cropped.height(), 2);
+        for (x, y) in (0..4).flat_map(|x| (0..2).map(move |y| (x, y))) {
+            assert_eq!(cropped.at(x, y), image.at(x + 2, y + 1));
+        }
+    }
+
+    #[test]
+    fn crop_widens_odd_columns_to_full_422_pixels() {
+        let image = numbered_image(4, 3);
+
+        let cropped = image.crop(Rectangle {
+            min: point![3.0, 0.0],
+            max: point![5.0, 1.0],
+        });
+
+        assert_eq!(cropped.width(), 4);
+        assert_eq!(cropped.height(), 1);
+        assert_eq!(cropped.at(0, 0), image.at(2, 0));
+        assert_eq!(cropped.at(3, 0), image.at(5, 0));
+    }
+
+    #[test]
+    fn crop_is_clamped_to_image() {
+        let image = numbered_image(4, 3);
+
+        let cropped = image.crop(Rectangle {
+            min: point![-5.0, -5.0],
+            max: point![100.0, 100.0],
+        });
+        assert_eq!(cropped.width(), image.width());
+        assert_eq!(cropped.height(), image.height());
+        assert_eq!(cropped.buffer(), image.buffer());
+
+        let empty = image.crop(Rectangle {
+            min: point![20.0, 1.0],
+            max: point![30.0, 2.0],
+        });
+        assert_eq!(empty.width(), 0);
+        assert!(empty.buffer().is_empty());
+    }
+
+    #[test]
+    fn loading_nonexistent_rgb_file_names_path() {
+        let error = YCbCr422Image::load_from_rgb_file("does/not/exist/image.png")
+            .expect_err("loading a nonexistent file succeeded");
+
+        assert!(format!("{error:?}").contains("image.png"));
+    }
+
+    #[test]
+    fn downsample_2x_averages_blocks() {
+        let image = numbered_image(4, 3);
+
+        let downsampled = image.downsample_2x();
+
+        assert_eq!(downsampled.width(), 4);
+        assert_eq!(downsampled.height(), 1);
+        // luma of the 2x2 block at (0, 0): 0, 1, 10, 11
+        assert_eq!(downsampled.at(0, 0).y, 5);
+        // luma of the 2x2 block at (2, 0): 2, 3, 12, 13
+        assert_eq!(downsampled.at(1, 0).y, 7);
+        // luma of the 2x2 block at (6, 0): 6, 7, 16, 17
+        assert_eq!(downsampled.at(3, 0).y, 11);
+        // chroma of 422 pixels 2 and 3 in rows 0 and 1
+        assert_eq!(downsampled.buffer()[1].cb, 2);
+        assert_eq!(downsampled.buffer()[1].cr, 0);
+    }
+}
@@ -9,7 +9,9 @@ use itertools::Itertools;
 use nalgebra::{distance, point, Point2};
 use projection::Projection;
 use types::{
-    detected_feet::{ClusterPoint, CountedCluster, DetectedFeet},
+    detected_feet::{
+        ClusterPoint, CountedCluster, DetectedFeet, DetectedFoot, DetectedFootInImage,
+    },
     Ball, CameraMatrix, EdgeType, FieldDimensions, FilteredSegments, LineData, ScanLine, Segment,
 };
 
@@ -22,6 +24,8 @@ pub struct CreationContext {}
 pub struct CycleContext {
     pub cluster_points: AdditionalOutput<Vec<ClusterPoint>, "feet_detection.cluster_points">,
     pub clusters_in_ground: AdditionalOutput<Vec<Point2<f32>>, "feet_detection.clusters_in_ground">,
+    pub detections_in_image:
+        AdditionalOutput<Vec<DetectedFootInImage>, "feet_detection.detections_in_image">,
 
     pub enable: Parameter<bool, "feet_detection.$cycler_instance.enable">,
     pub field_dimensions: Parameter<FieldDimensions, "field_dimensions">,
@@ -80,12 +84,28 @@ impl FeetDetection {
                 .map(|cluster| cluster.mean)
                 .collect()
         });
-        let positions = clusters_in_ground
-            .into_iter()
-            .map(|cluster| cluster.mean)
+        let feet: Vec<_> = clusters_in_ground
+            .iter()
+            .map(|cluster| DetectedFoot {
+                position_in_ground: cluster.mean,
+                confidence: cluster_confidence(cluster, *context.minimum_samples_per_cluster),
+            })
             .collect();
+        context.detections_in_image.fill_if_subscribed(|| {
+            feet.iter()
+                .filter_map(|foot| {
+                    Some(DetectedFootInImage {
+                        pixel_coordinates: context
+                            .camera_matrix
+                            .ground_to_pixel(foot.position_in_ground)
+                            .ok()?,
+                        confidence: foot.confidence,
+                    })
+                })
+                .collect()
+        });
         Ok(MainOutputs {
-            detected_feet: DetectedFeet { positions }.into(),
+            detected_feet: DetectedFeet { feet }.into(),
         })
     }
 }
@@ -170,6 +190,13 @@ fn find_last_consecutive_cluster(
         .last()
 }
 
+/// Approaches 1 with increasing segment support, a cluster just above the minimum scores about 0.5
+///
+/// Without a minimum number of samples every cluster scores exactly 1.
+fn cluster_confidence(cluster: &CountedCluster, minimum_samples_per_cluster: usize) -> f32 {
+    cluster.samples as f32 / (cluster.samples + minimum_samples_per_cluster) as f32
+}
+
 fn cluster_scored_cluster_points(
     cluster_points: Vec<ClusterPoint>,
     maximum_cluster_distance: f32,
@@ -199,3 +226,43 @@ fn cluster_scored_cluster_points(
     }
     clusters
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cluster_point(x: f32, y: f32) -> ClusterPoint {
+        ClusterPoint {
+            pixel_coordinates: Point2::origin(),
+            position_in_ground: point![x, y],
+        }
+    }
+
+    #[test]
+    fn well_supported_foot_is_more_confident_than_sparse_foot() {
+        let well_supported = (0..12).map(|index| cluster_point(1.0 + index as f32 * 0.01, 0.5));
+        let sparse = (0..4).map(|index| cluster_point(2.0, -0.5 + index as f32 * 0.01));
+        let cluster_points = well_supported.chain(sparse).collect();
+
+        let clusters = cluster_scored_cluster_points(cluster_points, 0.3);
+
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].samples, 12);
+        assert_eq!(clusters[1].samples, 4);
+        let well_supported_confidence = cluster_confidence(&clusters[0], 3);
+        let sparse_confidence = cluster_confidence(&clusters[1], 3);
+        assert!(well_supported_confidence > sparse_confidence);
+        assert!(well_supported_confidence < 1.0);
+        assert!(sparse_confidence > 0.0);
+    }
+
+    #[test]
+    fn confidence_is_one_without_minimum_samples() {
+        let cluster = CountedCluster {
+            mean: point![1.0, 0.5],
+            samples: 3,
+        };
+
+        assert_eq!(cluster_confidence(&cluster, 0), 1.0);
+    }
+}
@@ -17,11 +17,13 @@ pub struct CreationContext {}
 #[context]
 pub struct CycleContext {
     pub field_border_points: AdditionalOutput<Vec<Point2<f32>>, "field_border_points">,
+    pub rejected_field_border_lines: AdditionalOutput<Vec<Line2>, "rejected_field_border_lines">,
 
     pub angle_threshold: Parameter<f32, "field_border_detection.$cycler_instance.angle_threshold">,
     pub first_line_association_distance:
         Parameter<f32, "field_border_detection.$cycler_instance.first_line_association_distance">,
     pub horizon_margin: Parameter<f32, "field_border_detection.$cycler_instance.horizon_margin">,
+    pub maximum_slope: Parameter<f32, "field_border_detection.$cycler_instance.maximum_slope">,
     pub min_points_per_line:
         Parameter<usize, "field_border_detection.$cycler_instance.min_points_per_line">,
     pub second_line_association_distance:
@@ -71,6 +73,7 @@ impl FieldBorderDetection {
             .field_border_points
             .fill_if_subscribed(|| first_field_pixels.clone());
         let ransac = Ransac::new(first_field_pixels);
+        let mut rejected_lines = Vec::new();
         let border_lines = find_border_lines(
             ransac,
             context.camera_matrix,
@@ -78,7 +81,12 @@ impl FieldBorderDetection {
             *context.angle_threshold,
             *context.first_line_association_distance,
             *context.second_line_association_distance,
+            *context.maximum_slope,
+            &mut rejected_lines,
         );
+        context
+            .rejected_field_border_lines
+            .fill_if_subscribed(|| rejected_lines);
         Ok(MainOutputs {
             field_border: Some(FieldBorder { border_lines }).into(),
         })
@@ -90,10 +98,10 @@ fn get_first_field_segment<'segment>(
     horizon: &Horizon,
     horizon_margin: f32,
 ) -> Option<&'segment Segment> {
-    segments.iter().find(|segment| {
-        segment.field_color == Intensity::High
-            && segment.start > (horizon.horizon_y_minimum() + horizon_margin) as u16
-    })
+    let minimum_start = (horizon.horizon_y_minimum() + horizon_margin).max(0.0) as u16;
+    segments
+        .iter()
+        .find(|segment| segment.field_color == Intensity::High && segment.start > minimum_start)
 }
 
 fn find_border_lines(
@@ -103,25 +111,57 @@ fn find_border_lines(
     angle_threshold: f32,
     first_line_association_distance: f32,
     second_line_association_distance: f32,
+    maximum_slope: f32,
+    rejected_lines: &mut Vec<Line2>,
 ) -> Vec<Line2> {
     // first line
-    let result = ransac.next_line(20, first_line_association_distance);
-    if result.line.is_none() || result.used_points.len() < min_points_per_line {
+    let Some(first_line) = next_border_line(
+        &mut ransac,
+        min_points_per_line,
+        first_line_association_distance,
+        maximum_slope,
+        rejected_lines,
+    ) else {
         return Vec::new();
-    }
-    let first_line = best_fit_line(&result.used_points);
+    };
     // second line
-    let result = ransac.next_line(20, second_line_association_distance);
-    if result.line.is_none() || result.used_points.len() < min_points_per_line {
+    let Some(second_line) = next_border_line(
+        &mut ransac,
+        min_points_per_line,
+        second_line_association_distance,
+        maximum_slope,
+        rejected_lines,
+    ) else {
         return vec![first_line];
-    }
-    let second_line = best_fit_line(&result.used_points);
+    };
     if !is_orthogonal(&[first_line, second_line], camera_matrix, angle_threshold).unwrap_or(false) {
         return vec![first_line];
     }
     vec![first_line, second_line]
 }
 
+/// Fits lines until one is not steeper than `maximum_slope` in the image, collecting the others
+fn next_border_line(
+    ransac: &mut Ransac,
+    min_points_per_line: usize,
+    association_distance: f32,
+    maximum_slope: f32,
+    rejected_lines: &mut Vec<Line2>,
+) -> Option<Line2> {
+    loop {
+        let result = ransac.next_line(20, association_distance);
+        if result.line.is_none() || result.used_points.len() < min_points_per_line {
+            return None;
+        }
+        let line = best_fit_line(&result.used_points);
+        let direction = line.1 - line.0;
+        if direction.y.abs() <= maximum_slope * direction.x.abs() {
+            return Some(line);
+        }
+        rejected_lines.push(line);
+    }
+}
+
 fn best_fit_line(points: &[Point2<f32>]) -> Line2 {
     let half_size = points.len() / 2;
     let line_start = find_centre_of_group(&points[0..half_size]);
@@ -159,7 +199,10 @@ fn is_orthogonal(
 
 #[cfg(test)]
 mod test {
+    use std::f32::consts::FRAC_PI_4;
+
     use approx::assert_relative_eq;
+    use nalgebra::{vector, Isometry3};
     use rand::{rngs::StdRng, Rng, SeedableRng};
     use types::{EdgeType, ScanLine, YCbCr444};
 
@@ -217,6 +260,29 @@ mod test {
         assert_eq!(green_segment, Some(&scanline.segments[7]));
     }
 
+    #[test]
+    fn find_first_field_segment_with_horizon_above_image() {
+        let mut scanline = create_scanline(
+            YCbCr444 {
+                y: 20,
+                cb: 100,
+                cr: 150,
+            },
+            10,
+            10,
+        );
+        scanline.segments[1].field_color = Intensity::High;
+        let green_segment = get_first_field_segment(
+            &scanline.segments,
+            &Horizon {
+                left_horizon_y: -20.0,
+                right_horizon_y: -30.0,
+            },
+            15.0,
+        );
+        assert_eq!(green_segment, Some(&scanline.segments[1]));
+    }
+
     #[test]
     fn find_centre_of_two_points() {
         let points = vec![Point2::<f32>::new(2.0, 5.0), Point2::<f32>::new(4.0, 7.0)];
@@ -244,4 +310,38 @@ mod test {
         let calculated_centre = find_centre_of_group(&points);
         assert_relative_eq!(centre, calculated_centre, epsilon = 0.0001);
     }
+
+    #[test]
+    fn steep_border_line_is_rejected_in_favor_of_shallow_line() {
+        let steep_points = (0..80).map(|y| point![500.0, 100.0 + y as f32 * 4.0]);
+        let shallow_points = (0..50).map(|x| point![x as f32 * 8.0, 200.0 + x as f32 * 0.8]);
+        let ransac = Ransac::with_seed(steep_points.chain(shallow_points).collect(), 0);
+        let camera_matrix = CameraMatrix::from_normalized_focal_and_center(
+            vector![0.95, 1.27],
+            point![0.5, 0.5],
+            vector![640.0, 480.0],
+            Isometry3::identity(),
+            Isometry3::identity(),
+            Isometry3::new(vector![0.0, 0.0, 0.5], vector![0.0, FRAC_PI_4, 0.0]),
+        );
+        let mut rejected_lines = Vec::new();
+
+        let border_lines = find_border_lines(
+            ransac,
+            &camera_matrix,
+            10,
+            0.35,
+            2.0,
+            2.0,
+            1.0,
+            &mut rejected_lines,
+        );
+
+        assert_eq!(border_lines.len(), 1);
+        let direction = border_lines[0].1 - border_lines[0].0;
+        assert_relative_eq!(direction.y / direction.x, 0.1, epsilon = 0.001);
+        assert_eq!(rejected_lines.len(), 1);
+        let rejected_direction = rejected_lines[0].1 - rejected_lines[0].0;
+        assert_relative_eq!(rejected_direction.x, 0.0);
+    }
 }
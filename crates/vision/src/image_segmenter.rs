@@ -6,7 +6,6 @@ use framework::{AdditionalOutput, MainOutput};
 use nalgebra::point;
 use types::{
     configuration::{EdgeDetectionSource, MedianMode},
-    horizon::Horizon,
     is_above_limbs,
     ycbcr422_image::YCbCr422Image,
     CameraMatrix, EdgeType, FieldColor, ImageSegments, Intensity, Limb, ProjectedLimbs, Rgb,
@@ -41,6 +40,18 @@ pub struct CycleContext {
         Parameter<i16, "image_segmenter.$cycler_instance.vertical_edge_threshold">,
     pub vertical_median_mode:
         Parameter<MedianMode, "image_segmenter.$cycler_instance.vertical_median_mode">,
+    pub horizontal_scan_line_spacing:
+        Parameter<Option<usize>, "image_segmenter.$cycler_instance.horizontal_scan_line_spacing?">,
+    pub horizontal_scan_line_stride:
+        Parameter<usize, "image_segmenter.$cycler_instance.horizontal_scan_line_stride">,
+    pub horizontal_edge_detection_source: Parameter<
+        EdgeDetectionSource,
+        "image_segmenter.$cycler_instance.horizontal_edge_detection_source",
+    >,
+    pub horizontal_edge_threshold:
+        Parameter<i16, "image_segmenter.$cycler_instance.horizontal_edge_threshold">,
+    pub horizontal_median_mode:
+        Parameter<MedianMode, "image_segmenter.$cycler_instance.horizontal_median_mode">,
 }
 
 #[context]
@@ -66,18 +77,28 @@ impl ImageSegmenter {
                     }
                 });
 
-        let horizon = context
+        let horizon_row = context
             .camera_matrix
-            .map_or(Horizon::default(), |camera_matrix| camera_matrix.horizon);
+            .and_then(CameraMatrix::horizon_row)
+            .unwrap_or(0);
         let scan_grid = new_grid(
             context.image,
-            &horizon,
+            horizon_row,
             context.field_color,
             *context.horizontal_stride,
             *context.vertical_stride,
             *context.vertical_edge_detection_source,
             *context.vertical_edge_threshold,
             *context.vertical_median_mode,
+            context
+                .horizontal_scan_line_spacing
+                .map(|&spacing| HorizontalScanLineParameters {
+                    spacing,
+                    stride: *context.horizontal_scan_line_stride,
+                    edge_detection_source: *context.horizontal_edge_detection_source,
+                    edge_threshold: *context.horizontal_edge_threshold,
+                    median_mode: *context.horizontal_median_mode,
+                }),
             projected_limbs,
         );
         let end = Instant::now();
@@ -90,21 +111,29 @@ impl ImageSegmenter {
     }
 }
 
+#[derive(Clone, Copy)]
+struct HorizontalScanLineParameters {
+    spacing: usize,
+    stride: usize,
+    edge_detection_source: EdgeDetectionSource,
+    edge_threshold: i16,
+    median_mode: MedianMode,
+}
+
 #[allow(clippy::too_many_arguments)]
 fn new_grid(
     image: &YCbCr422Image,
-    horizon: &Horizon,
+    horizon_row: u32,
     field_color: &FieldColor,
     horizontal_stride: usize,
     vertical_stride: usize,
     vertical_edge_detection_source: EdgeDetectionSource,
     vertical_edge_threshold: i16,
     vertical_median_mode: MedianMode,
+    horizontal_scan_line_parameters: Option<HorizontalScanLineParameters>,
     projected_limbs: &[Limb],
 ) -> ScanGrid {
-    let horizon_y_minimum = horizon
-        .horizon_y_minimum()
-        .clamp(0.0, image.height() as f32);
+    let horizon_y_minimum = horizon_row.min(image.height()) as f32;
 
     ScanGrid {
         vertical_scan_lines: (0..image.width())
@@ -123,6 +152,26 @@ fn new_grid(
                 )
             })
             .collect(),
+        horizontal_scan_lines: horizontal_scan_line_parameters.map_or_else(
+            Vec::new,
+            |parameters| {
+                (horizon_y_minimum as u32..image.height())
+                    .step_by(parameters.spacing)
+                    .map(|y| {
+                        new_horizontal_scan_line(
+                            image,
+                            field_color,
+                            y,
+                            parameters.stride,
+                            parameters.edge_detection_source,
+                            parameters.edge_threshold,
+                            parameters.median_mode,
+                            projected_limbs,
+                        )
+                    })
+                    .collect()
+            },
+        ),
     }
 }
 
@@ -302,6 +351,100 @@ fn new_vertical_scan_line(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+fn new_horizontal_scan_line(
+    image: &YCbCr422Image,
+    field_color: &FieldColor,
+    position: u32,
+    stride: usize,
+    edge_detection_source: EdgeDetectionSource,
+    edge_threshold: i16,
+    median_mode: MedianMode,
+    projected_limbs: &[Limb],
+) -> ScanLine {
+    let (start_x, end_x) = match median_mode {
+        MedianMode::Disabled => (0, image.width()),
+        MedianMode::ThreePixels => (1, image.width() - 1),
+        MedianMode::FivePixels => (2, image.width() - 2),
+    };
+    if start_x >= end_x {
+        return ScanLine {
+            position: position as u16,
+            segments: Vec::new(),
+        };
+    }
+
+    let luminance_value_of_first_pixel = horizontal_edge_detection_value(
+        image,
+        start_x,
+        position,
+        edge_detection_source,
+        median_mode,
+    );
+    let mut state = ScanLineState::new(luminance_value_of_first_pixel, 0, EdgeType::ImageBorder);
+
+    let mut segments = Vec::with_capacity((end_x - start_x) as usize / stride);
+    for x in (start_x..end_x).step_by(stride) {
+        let luminance_value =
+            horizontal_edge_detection_value(image, x, position, edge_detection_source, median_mode);
+        if let Some(segment) = detect_edge(&mut state, x as u16, luminance_value, edge_threshold) {
+            segments.push(set_color_in_horizontal_segment(
+                segment,
+                image,
+                position,
+                field_color,
+            ));
+        }
+    }
+
+    let last_segment = Segment {
+        start: state.start_position,
+        end: image.width() as u16,
+        start_edge_type: state.start_edge_type,
+        end_edge_type: EdgeType::ImageBorder,
+        color: Default::default(),
+        field_color: Intensity::Low,
+    };
+    segments.push(set_color_in_horizontal_segment(
+        last_segment,
+        image,
+        position,
+        field_color,
+    ));
+    segments.retain(|segment| {
+        is_above_limbs(
+            point![segment.center() as f32, position as f32],
+            projected_limbs,
+        )
+    });
+
+    ScanLine {
+        position: position as u16,
+        segments,
+    }
+}
+
+fn horizontal_edge_detection_value(
+    image: &YCbCr422Image,
+    x: u32,
+    y: u32,
+    edge_detection_source: EdgeDetectionSource,
+    median_mode: MedianMode,
+) -> i16 {
+    let value_at = |x| pixel_to_edge_detection_value(image.at(x, y), edge_detection_source);
+    (match median_mode {
+        MedianMode::Disabled => value_at(x),
+        MedianMode::ThreePixels => median_of_three(value_at(x - 1), value_at(x), value_at(x + 1)),
+        MedianMode::FivePixels => median_of_five(
+            value_at(x - 2),
+            value_at(x - 1),
+            value_at(x),
+            value_at(x + 1),
+            value_at(x + 2),
+        ),
+    }) as i16
+}
+
 fn pixel_to_edge_detection_value(
     pixel: YCbCr444,
     edge_detection_source: EdgeDetectionSource,
@@ -312,6 +455,20 @@ fn pixel_to_edge_detection_value(
             let rgb = Rgb::from(pixel);
             (rgb.get_chromaticity(RgbChannel::Green) * 255.0) as u8
         }
+        EdgeDetectionSource::SaturationOfHsv => {
+            let rgb = Rgb::from(pixel);
+            let maximum = rgb.r.max(rgb.g).max(rgb.b);
+            let minimum = rgb.r.min(rgb.g).min(rgb.b);
+            if maximum == 0 {
+                0
+            } else {
+                ((maximum - minimum) as u16 * 255 / maximum as u16) as u8
+            }
+        }
+        EdgeDetectionSource::ValueOfHsv => {
+            let rgb = Rgb::from(pixel);
+            rgb.r.max(rgb.g).max(rgb.b)
+        }
     }
 }
 
@@ -403,6 +560,34 @@ fn set_color_in_vertical_segment(
     segment
 }
 
+fn set_color_in_horizontal_segment(
+    mut segment: Segment,
+    image: &YCbCr422Image,
+    y: u32,
+    field_color: &FieldColor,
+) -> Segment {
+    segment.color = if segment.length() >= 4 {
+        let spacing = segment.length() / 4;
+        let first_position = segment.start + spacing;
+        let second_position = segment.start + 2 * spacing;
+        let third_position = segment.start + 3 * spacing;
+
+        let first_pixel = image.at(first_position as u32, y);
+        let second_pixel = image.at(second_position as u32, y);
+        let third_pixel = image.at(third_position as u32, y);
+
+        let y = median_of_three(first_pixel.y, second_pixel.y, third_pixel.y);
+        let cb = median_of_three(first_pixel.cb, second_pixel.cb, third_pixel.cb);
+        let cr = median_of_three(first_pixel.cr, second_pixel.cr, third_pixel.cr);
+        YCbCr444::new(y, cb, cr)
+    } else {
+        let position = segment.start + segment.length() / 2;
+        image.at(position as u32, y)
+    };
+    segment.field_color = field_color.get_intensity(segment.color);
+    segment
+}
+
 fn segment_is_below_limbs(
     scan_line_position: u16,
     segment: &Segment,
@@ -1446,4 +1631,164 @@ mod tests {
             assert_eq!(calculated_median,real_median, "test_case: {first} {second} {third} {fourth} {fifth}, median_of_five: {calculated_median}");
         }
     }
+
+    #[test]
+    fn hsv_edge_detection_values_of_gray_pixel() {
+        let gray = YCbCr444::new(200, 128, 128);
+        assert_eq!(
+            pixel_to_edge_detection_value(gray, EdgeDetectionSource::SaturationOfHsv),
+            0
+        );
+        assert_eq!(
+            pixel_to_edge_detection_value(gray, EdgeDetectionSource::ValueOfHsv),
+            200
+        );
+    }
+
+    #[test]
+    fn hsv_edge_detection_values_of_saturated_pixel() {
+        let green = YCbCr444::from(Rgb::new(0, 200, 0));
+        assert_eq!(
+            pixel_to_edge_detection_value(green, EdgeDetectionSource::SaturationOfHsv),
+            255
+        );
+        assert_eq!(
+            pixel_to_edge_detection_value(green, EdgeDetectionSource::ValueOfHsv),
+            200
+        );
+    }
+
+    #[test]
+    fn image_with_horizontal_bar_in_right_half() {
+        let dark = YCbCr422::new(0, 128, 0, 128);
+        let bright = YCbCr422::new(100, 128, 100, 128);
+        let image = YCbCr422Image::from_ycbcr_buffer(
+            4,
+            4,
+            iproduct!(0..4, 0..4)
+                .map(|(y, x)| {
+                    if (1..3).contains(&y) && x >= 2 {
+                        bright
+                    } else {
+                        dark
+                    }
+                })
+                .collect(),
+        );
+        let field_color = FieldColor {
+            red_chromaticity_threshold: 0.37,
+            blue_chromaticity_threshold: 0.38,
+            lower_green_chromaticity_threshold: 0.4,
+            upper_green_chromaticity_threshold: 0.43,
+            green_luminance_threshold: 255,
+        };
+
+        let scan_line_above_bar = new_horizontal_scan_line(
+            &image,
+            &field_color,
+            0,
+            1,
+            EdgeDetectionSource::Luminance,
+            10,
+            MedianMode::Disabled,
+            &[],
+        );
+        assert_eq!(scan_line_above_bar.position, 0);
+        assert_eq!(scan_line_above_bar.segments.len(), 1);
+        assert_eq!(scan_line_above_bar.segments[0].start, 0);
+        assert_eq!(scan_line_above_bar.segments[0].end, 8);
+        assert_eq!(scan_line_above_bar.segments[0].color.y, 0);
+
+        let scan_line_in_bar = new_horizontal_scan_line(
+            &image,
+            &field_color,
+            1,
+            1,
+            EdgeDetectionSource::Luminance,
+            10,
+            MedianMode::Disabled,
+            &[],
+        );
+        assert_eq!(scan_line_in_bar.position, 1);
+        assert_eq!(scan_line_in_bar.segments.len(), 2);
+        assert_eq!(scan_line_in_bar.segments[0].start, 0);
+        assert_eq!(scan_line_in_bar.segments[0].end, 4);
+        assert_eq!(scan_line_in_bar.segments[0].end_edge_type, EdgeType::Rising);
+        assert_eq!(scan_line_in_bar.segments[0].color.y, 0);
+        assert_eq!(scan_line_in_bar.segments[1].start, 4);
+        assert_eq!(scan_line_in_bar.segments[1].end, 8);
+        assert_eq!(scan_line_in_bar.segments[1].color.y, 100);
+    }
+
+    #[test]
+    fn horizontal_scan_lines_are_only_generated_when_enabled() {
+        let image = YCbCr422Image::zero(8, 8);
+        let field_color = FieldColor {
+            red_chromaticity_threshold: 0.37,
+            blue_chromaticity_threshold: 0.38,
+            lower_green_chromaticity_threshold: 0.4,
+            upper_green_chromaticity_threshold: 0.43,
+            green_luminance_threshold: 255,
+        };
+        let new_grid_with_spacing = |spacing: Option<usize>| {
+            new_grid(
+                &image,
+                0,
+                &field_color,
+                4,
+                2,
+                EdgeDetectionSource::Luminance,
+                10,
+                MedianMode::Disabled,
+                spacing.map(|spacing| HorizontalScanLineParameters {
+                    spacing,
+                    stride: 1,
+                    edge_detection_source: EdgeDetectionSource::Luminance,
+                    edge_threshold: 10,
+                    median_mode: MedianMode::Disabled,
+                }),
+                &[],
+            )
+        };
+
+        assert!(new_grid_with_spacing(None).horizontal_scan_lines.is_empty());
+        let positions: Vec<_> = new_grid_with_spacing(Some(3))
+            .horizontal_scan_lines
+            .iter()
+            .map(|scan_line| scan_line.position)
+            .collect();
+        assert_eq!(positions, vec![0, 3, 6]);
+    }
+
+    #[test]
+    fn horizontal_median_suppresses_single_pixel_spike() {
+        let dark = YCbCr422::new(0, 128, 0, 128);
+        let spike = YCbCr422::new(0, 128, 100, 128);
+        let image = YCbCr422Image::from_ycbcr_buffer(4, 1, vec![dark, spike, dark, dark]);
+        let field_color = FieldColor {
+            red_chromaticity_threshold: 0.37,
+            blue_chromaticity_threshold: 0.38,
+            lower_green_chromaticity_threshold: 0.4,
+            upper_green_chromaticity_threshold: 0.43,
+            green_luminance_threshold: 255,
+        };
+        let new_scan_line = |median_mode| {
+            new_horizontal_scan_line(
+                &image,
+                &field_color,
+                0,
+                1,
+                EdgeDetectionSource::Luminance,
+                10,
+                median_mode,
+                &[],
+            )
+        };
+
+        assert!(new_scan_line(MedianMode::Disabled).segments.len() > 1);
+        let scan_line = new_scan_line(MedianMode::ThreePixels);
+        assert_eq!(scan_line.segments.len(), 1);
+        assert_eq!(scan_line.segments[0].start, 0);
+        assert_eq!(scan_line.segments[0].end, 8);
+    }
 }
@@ -3,7 +3,7 @@ use std::{collections::HashSet, ops::Range};
 use color_eyre::Result;
 use context_attribute::context;
 use framework::{AdditionalOutput, MainOutput};
-use nalgebra::{distance, point, vector, Point2, Vector2};
+use nalgebra::{distance, distance_squared, point, vector, Point2, Vector2};
 use ordered_float::NotNan;
 use projection::Projection;
 use types::{
@@ -40,6 +40,7 @@ pub struct CycleContext {
         Parameter<f32, "line_detection.$cycler_instance.maximum_projected_segment_length">,
     pub minimum_number_of_points_on_line:
         Parameter<usize, "line_detection.$cycler_instance.minimum_number_of_points_on_line">,
+    pub ransac_seed: Parameter<Option<u64>, "line_detection.$cycler_instance.ransac_seed?">,
 
     pub camera_matrix: RequiredInput<Option<CameraMatrix>, "camera_matrix?">,
     pub filtered_segments: Input<FilteredSegments, "filtered_segments">,
@@ -74,27 +75,39 @@ impl LineDetection {
         if context.lines_in_image.is_subscribed() {
             lines_in_image.points = line_points.clone();
         }
-        let mut ransac = Ransac::new(line_points);
+        let mut ransac = match context.ransac_seed {
+            Some(seed) => Ransac::with_seed(line_points, *seed),
+            None => Ransac::new(line_points),
+        };
         let mut lines_in_robot = Vec::new();
         for _ in 0..*context.maximum_number_of_lines {
-            if ransac.unused_points.len() < *context.minimum_number_of_points_on_line {
+            if ransac.number_of_unused_points() < *context.minimum_number_of_points_on_line {
                 break;
             }
-            let RansacResult {
-                line: ransac_line,
-                used_points,
-            } = ransac.next_line(20, *context.maximum_fit_distance_in_pixels);
+            let (
+                RansacResult {
+                    line: ransac_line,
+                    used_points,
+                    segment_endpoints,
+                },
+                used_point_indices,
+            ) = ransac.next_line_with_indices(20, *context.maximum_fit_distance_in_pixels);
             let ransac_line =
                 ransac_line.expect("Insufficient number of line points. Cannot fit line.");
             if used_points.len() < *context.minimum_number_of_points_on_line {
                 break;
             }
+            let Some((segment_start, _segment_end)) = segment_endpoints else {
+                break;
+            };
             let mut points_with_projection_onto_line: Vec<_> = used_points
                 .iter()
-                .map(|&point| (point, ransac_line.project_point(point)))
+                .zip(used_point_indices)
+                .map(|(&point, index)| (point, ransac_line.project_point(point), index))
                 .collect();
-            points_with_projection_onto_line.sort_by_key(|(_point, projected_point)| {
-                NotNan::new(projected_point.x).expect("Tried to compare NaN")
+            points_with_projection_onto_line.sort_by_key(|(_point, projected_point, _index)| {
+                NotNan::new(distance_squared(&segment_start, projected_point))
+                    .expect("Tried to compare NaN")
             });
             let split_index = (1..points_with_projection_onto_line.len())
                 .find(|&index| {
@@ -105,16 +118,18 @@ impl LineDetection {
                 })
                 .unwrap_or(points_with_projection_onto_line.len());
             let after_gap = points_with_projection_onto_line.split_off(split_index);
-            ransac
-                .unused_points
-                .extend(after_gap.iter().map(|(point, _projected_point)| point));
+            ransac.reuse_points(
+                after_gap
+                    .iter()
+                    .map(|(_point, _projected_point, index)| *index),
+            );
             if points_with_projection_onto_line.len() < *context.minimum_number_of_points_on_line {
                 // just drop and ignore this line
                 continue;
             }
             let (start_point_in_image, start_point_in_robot) =
                 match points_with_projection_onto_line.iter().copied().find_map(
-                    |(point, projected_point)| {
+                    |(point, projected_point, _index)| {
                         Some((
                             point,
                             context
@@ -131,7 +146,7 @@ impl LineDetection {
                 .iter()
                 .copied()
                 .rev()
-                .find_map(|(point, projected_point)| {
+                .find_map(|(point, projected_point, _index)| {
                     Some((
                         point,
                         context
@@ -351,6 +366,7 @@ mod tests {
             FilteredSegments {
                 scan_grid: ScanGrid {
                     vertical_scan_lines,
+                    horizontal_scan_lines: vec![],
                 },
             }
         }
@@ -88,7 +88,9 @@ fn generate_rows(
 
     let mut rows = vec![];
 
-    while row_vertical_center >= higher_horizon_point.y && row_vertical_center + ball_radius > 0.0 {
+    while camera_matrix.below_horizon(point![higher_horizon_point.x, row_vertical_center])
+        && row_vertical_center + ball_radius > 0.0
+    {
         radius = camera_matrix
             .get_pixel_radius(
                 ball_radius,
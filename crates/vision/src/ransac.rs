@@ -1,4 +1,6 @@
+use itertools::Itertools;
 use nalgebra::Point2;
+use ordered_float::NotNan;
 use rand::{rngs::StdRng, seq::SliceRandom, thread_rng, SeedableRng};
 use types::{Line, Line2};
 
@@ -6,43 +8,91 @@ use types::{Line, Line2};
 pub struct RansacResult {
     pub line: Option<Line2>,
     pub used_points: Vec<Point2<f32>>,
+    /// Projections of the outermost used points onto the line
+    pub segment_endpoints: Option<(Point2<f32>, Point2<f32>)>,
 }
 
 pub struct Ransac {
-    pub unused_points: Vec<Point2<f32>>,
+    points: Vec<Point2<f32>>,
+    unused_point_indices: Vec<usize>,
     random_number_generator: StdRng,
 }
 
 impl Ransac {
-    pub fn new(unused_points: Vec<Point2<f32>>) -> Self {
+    pub fn new(points: Vec<Point2<f32>>) -> Self {
+        Self::with_random_number_generator(
+            points,
+            StdRng::from_rng(thread_rng()).expect("Failed to create random number generator"),
+        )
+    }
+
+    pub fn with_seed(points: Vec<Point2<f32>>, seed: u64) -> Self {
+        Self::with_random_number_generator(points, StdRng::seed_from_u64(seed))
+    }
+
+    fn with_random_number_generator(
+        points: Vec<Point2<f32>>,
+        random_number_generator: StdRng,
+    ) -> Self {
         Self {
-            unused_points,
-            random_number_generator: StdRng::from_rng(thread_rng())
-                .expect("Failed to create random number generator"),
+            unused_point_indices: (0..points.len()).collect(),
+            points,
+            random_number_generator,
         }
     }
+
+    pub fn number_of_unused_points(&self) -> usize {
+        self.unused_point_indices.len()
+    }
+
+    /// Makes previously used points available for the following lines again
+    ///
+    /// Indices refer to the points passed on construction, as returned by
+    /// [`Ransac::next_line_with_indices`].
+    pub fn reuse_points(&mut self, indices: impl IntoIterator<Item = usize>) {
+        self.unused_point_indices.extend(indices);
+    }
 }
 
 impl Ransac {
     pub fn next_line(&mut self, iterations: usize, maximum_distance: f32) -> RansacResult {
-        if self.unused_points.len() < 2 {
-            return RansacResult {
-                line: None,
-                used_points: vec![],
-            };
+        self.next_line_with_indices(iterations, maximum_distance).0
+    }
+
+    /// Like [`Ransac::next_line`], but additionally returns the indices of the used points.
+    ///
+    /// Indices refer to the points passed on construction.
+    pub fn next_line_with_indices(
+        &mut self,
+        iterations: usize,
+        maximum_distance: f32,
+    ) -> (RansacResult, Vec<usize>) {
+        if self.unused_point_indices.len() < 2 {
+            return (
+                RansacResult {
+                    line: None,
+                    used_points: vec![],
+                    segment_endpoints: None,
+                },
+                vec![],
+            );
         }
+        let points = &self.points;
         let maximum_distance_squared = maximum_distance * maximum_distance;
         let best_line = (0..iterations)
             .map(|_| {
-                let mut points = self
-                    .unused_points
+                let mut indices = self
+                    .unused_point_indices
                     .choose_multiple(&mut self.random_number_generator, 2);
-                let line = Line(*points.next().unwrap(), *points.next().unwrap());
+                let line = Line(
+                    points[*indices.next().unwrap()],
+                    points[*indices.next().unwrap()],
+                );
                 let score = self
-                    .unused_points
+                    .unused_point_indices
                     .iter()
-                    .filter(|point| {
-                        line.squared_distance_to_point(**point) <= maximum_distance_squared
+                    .filter(|index| {
+                        line.squared_distance_to_point(points[**index]) <= maximum_distance_squared
                     })
                     .count();
                 (line, score)
@@ -50,17 +100,40 @@ impl Ransac {
             .max_by_key(|scored_line| scored_line.1)
             .expect("max_by_key erroneously returned no result")
             .0;
-        let (used_points, unused_points) = self.unused_points.iter().partition(|point| {
-            best_line.squared_distance_to_point(**point) <= maximum_distance_squared
-        });
-        self.unused_points = unused_points;
-        RansacResult {
-            line: Some(best_line),
-            used_points,
-        }
+        let (used_point_indices, unused_point_indices): (Vec<_>, Vec<_>) = self
+            .unused_point_indices
+            .iter()
+            .copied()
+            .partition(|index| {
+                best_line.squared_distance_to_point(points[*index]) <= maximum_distance_squared
+            });
+        self.unused_point_indices = unused_point_indices;
+        let used_points: Vec<_> = used_point_indices
+            .iter()
+            .map(|index| points[*index])
+            .collect();
+        let segment_endpoints = inlier_extent(&best_line, &used_points);
+        (
+            RansacResult {
+                line: Some(best_line),
+                used_points,
+                segment_endpoints,
+            },
+            used_point_indices,
+        )
     }
 }
 
+fn inlier_extent(line: &Line2, points: &[Point2<f32>]) -> Option<(Point2<f32>, Point2<f32>)> {
+    let direction = line.1 - line.0;
+    let (first, last) = points
+        .iter()
+        .filter_map(|point| Some((NotNan::new((point - line.0).dot(&direction)).ok()?, point)))
+        .minmax_by_key(|(parameter, _point)| *parameter)
+        .into_option()?;
+    Some((line.project_point(*first.1), line.project_point(*last.1)))
+}
+
 #[cfg(test)]
 mod test {
     use approx::assert_relative_eq;
@@ -69,10 +142,7 @@ mod test {
     use super::*;
 
     fn ransac_with_seed(unused_points: Vec<Point2<f32>>, seed: u64) -> Ransac {
-        Ransac {
-            unused_points,
-            random_number_generator: StdRng::seed_from_u64(seed),
-        }
+        Ransac::with_seed(unused_points, seed)
     }
 
     #[test]
@@ -114,4 +184,87 @@ mod test {
         assert_relative_eq!(line.y_axis_intercept(), y_intercept, epsilon = 0.0001);
         assert_eq!(result.used_points, points);
     }
+
+    #[test]
+    fn ransac_same_seed_is_reproducible() {
+        let points: Vec<Point2<f32>> = (0..50)
+            .map(|x| point![x as f32, (x * x % 17) as f32])
+            .collect();
+
+        let mut first = ransac_with_seed(points.clone(), 42);
+        let mut second = ransac_with_seed(points, 42);
+        for _ in 0..3 {
+            assert_eq!(first.next_line(5, 1.0), second.next_line(5, 1.0));
+        }
+    }
+
+    #[test]
+    fn ransac_indices_refer_to_construction_input() {
+        let points: Vec<Point2<f32>> = (0..20)
+            .map(|index| {
+                if index % 4 == 3 {
+                    point![
+                        index as f32 * 10.0,
+                        50.0 + (index * index % 13) as f32 * 10.0
+                    ]
+                } else {
+                    point![index as f32 * 10.0, 0.0]
+                }
+            })
+            .collect();
+
+        let mut ransac = ransac_with_seed(points.clone(), 0);
+        let (result, used_point_indices) = ransac.next_line_with_indices(30, 1.0);
+
+        let expected_indices: Vec<usize> = (0..20).filter(|index| index % 4 != 3).collect();
+        assert_eq!(used_point_indices, expected_indices);
+        let expected_points: Vec<Point2<f32>> = expected_indices
+            .iter()
+            .map(|&index| points[index])
+            .collect();
+        assert_eq!(result.used_points, expected_points);
+    }
+
+    #[test]
+    fn ransac_reused_points_keep_their_indices() {
+        let points = vec![
+            point![0.0, 0.0],
+            point![10.0, 0.0],
+            point![0.0, 5.0],
+            point![10.0, 5.0],
+        ];
+        let mut ransac = ransac_with_seed(points, 0);
+        let (_, first_indices) = ransac.next_line_with_indices(10, 1.0);
+        let (_, second_indices) = ransac.next_line_with_indices(10, 1.0);
+        assert_eq!(ransac.number_of_unused_points(), 0);
+
+        ransac.reuse_points(first_indices.iter().copied());
+        assert_eq!(ransac.number_of_unused_points(), 2);
+        let (result, reused_indices) = ransac.next_line_with_indices(10, 1.0);
+        assert_eq!(reused_indices, first_indices);
+        assert_ne!(reused_indices, second_indices);
+        assert_eq!(result.used_points.len(), 2);
+    }
+
+    #[test]
+    fn ransac_segment_endpoints_span_inliers() {
+        let points: Vec<Point2<f32>> = [7.0, -3.0, 12.5, 0.0, 4.0]
+            .into_iter()
+            .map(|x| point![x, 2.0 * x + 1.0])
+            .chain([point![50.0, -40.0]])
+            .collect();
+
+        let mut ransac = ransac_with_seed(points, 0);
+        let result = ransac.next_line(30, 0.5);
+        let (start, end) = result.segment_endpoints.expect("No segment was found");
+
+        let (start, end) = if start.x < end.x {
+            (start, end)
+        } else {
+            (end, start)
+        };
+        assert_relative_eq!(start, point![-3.0, -5.0], epsilon = 1e-4);
+        assert_relative_eq!(end, point![12.5, 26.0], epsilon = 1e-4);
+        assert_eq!(result.used_points.len(), 5);
+    }
 }
@@ -33,6 +33,10 @@ impl SegmentFilter {
                     &context.image_segments.scan_grid.vertical_scan_lines,
                     context.field_border,
                 ),
+                horizontal_scan_lines: filter_horizontal_scan_lines(
+                    &context.image_segments.scan_grid.horizontal_scan_lines,
+                    context.field_border,
+                ),
             },
         };
         Ok(MainOutputs {
@@ -74,3 +78,37 @@ fn filter_vertical_segments(
         .copied()
         .collect()
 }
+
+fn filter_horizontal_scan_lines(
+    scan_lines: &[ScanLine],
+    field_border: Option<&FieldBorder>,
+) -> Vec<ScanLine> {
+    scan_lines
+        .iter()
+        .map(|scan_line| ScanLine {
+            position: scan_line.position,
+            segments: filter_horizontal_segments(
+                scan_line.position,
+                &scan_line.segments,
+                field_border,
+            ),
+        })
+        .collect()
+}
+
+fn filter_horizontal_segments(
+    scan_line_position: u16,
+    segments: &[Segment],
+    field_border: Option<&FieldBorder>,
+) -> Vec<Segment> {
+    segments
+        .iter()
+        .filter(|segment| segment.field_color == Intensity::Low)
+        .filter(|segment| match field_border {
+            Some(field_border) => field_border
+                .is_inside_field(point![segment.center() as f32, scan_line_position as f32]),
+            None => true,
+        })
+        .copied()
+        .collect()
+}
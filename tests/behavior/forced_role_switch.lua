@@ -0,0 +1,61 @@
+local inspect = require 'inspect'
+
+local configured_role = "Keeper"
+
+function spawn_robot(number)
+    local robot = create_robot(number)
+    robot.configuration.role_assignment.forced_role = configured_role
+    table.insert(state.robots, robot)
+end
+
+spawn_robot(3)
+
+state.ball = {
+    position = { 2.0, 0.0 },
+    velocity = { 0.0, 0.0 },
+}
+
+local role_switch_time = 12.0
+local role_clear_time = 2 * role_switch_time
+
+function assert_role(expected_role)
+    local role = get_main_outputs(3).role
+    assert(
+        role == expected_role,
+        "expected " .. expected_role .. " at " .. state.time_elapsed .. "s but got " .. inspect(role)
+    )
+end
+
+function on_cycle()
+    if state.cycle_count == 1 then
+        force_role(3, 0.0, "Striker")
+        force_role(3, role_switch_time, "DefenderLeft")
+        clear_forced_role(3, role_clear_time)
+    end
+
+    if state.cycle_count % 500 == 0 then
+        print(inspect(state))
+    end
+
+    if state.cycle_count == 100 then
+        state.filtered_game_state = {
+            Playing = {
+                ball_is_free = true
+            }
+        }
+    end
+
+    if state.cycle_count % 100 == 0 then
+        if state.time_elapsed < role_switch_time then
+            assert_role("Striker")
+        elseif state.time_elapsed > role_switch_time + 0.1 and state.time_elapsed < role_clear_time then
+            assert_role("DefenderLeft")
+        elseif state.time_elapsed > role_clear_time + 0.1 then
+            assert_role(configured_role)
+        end
+    end
+
+    if state.time_elapsed > role_clear_time + role_switch_time then
+        state.finished = true
+    end
+end
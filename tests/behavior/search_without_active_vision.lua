@@ -0,0 +1,60 @@
+local inspect = require 'inspect'
+
+function spawn_robot(number)
+    table.insert(state.robots, create_robot(number))
+end
+
+spawn_robot(3)
+spawn_robot(4)
+
+state.ball = {
+    position = { 0.0, 0.0 },
+    velocity = { 0.0, 0.0 },
+}
+
+function on_cycle()
+    if state.cycle_count % 1000 == 0 then
+        print(inspect(state))
+    end
+
+    if state.cycle_count == 100 then
+        state.filtered_game_state = {
+            Playing = {
+                ball_is_free = true
+            }
+        }
+    end
+
+    if state.cycle_count == 200 then
+        state.ball = nil
+    end
+
+    if state.cycle_count == 1000 then
+        assert(not pcall(disable_node, 3, "ActiveVison"), "misspelled node name was accepted")
+        disable_node(3, "ActiveVision")
+    end
+
+    if state.cycle_count == 2000 then
+        local position_of_interest = get_main_outputs(3).position_of_interest
+        assert(
+            position_of_interest[1] == 0.0 and position_of_interest[2] == 0.0,
+            "disabled ActiveVision still produced " .. inspect(position_of_interest)
+        )
+    end
+
+    if state.cycle_count == 2500 then
+        enable_node(3, "ActiveVision")
+    end
+
+    if state.cycle_count == 3000 then
+        local position_of_interest = get_main_outputs(3).position_of_interest
+        assert(
+            position_of_interest[1] ~= 0.0 or position_of_interest[2] ~= 0.0,
+            "enabled ActiveVision produced no position of interest"
+        )
+    end
+
+    if state.cycle_count == 4000 then
+        state.finished = true
+    end
+end
@@ -0,0 +1,55 @@
+local inspect = require 'inspect'
+
+function spawn_robot(number)
+    table.insert(state.robots, create_robot(number))
+end
+
+spawn_robot(3)
+spawn_robot(4)
+
+state.ball = {
+    position = { 1.0, 0.0 },
+    velocity = { 0.0, 0.0 },
+}
+
+local stabilization_cycle = 2000
+local final_cycle = 3000
+local stable_roles = nil
+
+function assert_stable_roles()
+    local roles = { get_main_outputs(3).role, get_main_outputs(4).role }
+    assert(
+        roles[1] ~= roles[2],
+        "expected distinct roles at cycle " .. state.cycle_count .. " but got " .. inspect(roles)
+    )
+    if stable_roles == nil then
+        stable_roles = roles
+    end
+    assert(
+        roles[1] == stable_roles[1] and roles[2] == stable_roles[2],
+        "roles changed from " .. inspect(stable_roles) .. " to " .. inspect(roles)
+            .. " at cycle " .. state.cycle_count
+    )
+end
+
+function on_cycle()
+    if state.cycle_count % 500 == 0 then
+        print(inspect(state.messages))
+    end
+
+    if state.cycle_count == 100 then
+        state.filtered_game_state = {
+            Playing = {
+                ball_is_free = true
+            }
+        }
+    end
+
+    if state.cycle_count >= stabilization_cycle and state.cycle_count % 100 == 0 then
+        assert_stable_roles()
+    end
+
+    if state.cycle_count == final_cycle then
+        state.finished = true
+    end
+end
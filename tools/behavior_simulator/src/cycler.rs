@@ -1,4 +1,8 @@
-use std::{collections::BTreeMap, sync::Arc, time::SystemTime};
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+    time::SystemTime,
+};
 
 use color_eyre::{eyre::WrapErr, Result};
 use control::{
@@ -14,7 +18,18 @@ use cyclers::control::Database;
 use framework::{AdditionalOutput, PerceptionInput};
 use structs::Configuration;
 use tokio::sync::Notify;
-use types::{hardware, messages::IncomingMessage};
+use types::{hardware, messages::IncomingMessage, Role};
+
+/// Names of the nodes that can be disabled in [`BehaviorCycler::cycle`]
+pub const NODE_NAMES: &[&str] = &[
+    "RuleObstacleComposer",
+    "RoleAssignment",
+    "BallStateComposer",
+    "ActiveVision",
+    "KickSelector",
+    "WorldStateComposer",
+    "Behavior",
+];
 
 pub struct BehaviorCycler<Interface> {
     hardware_interface: Arc<Interface>,
@@ -85,12 +100,16 @@ where
         own_database: &mut Database,
         configuration: &Configuration,
         incoming_messages: BTreeMap<SystemTime, Vec<&IncomingMessage>>,
+        disabled_nodes: &HashSet<String>,
+        forced_roles: &BTreeMap<SystemTime, Option<Role>>,
     ) -> Result<()> {
-        if own_database
-            .main_outputs
-            .game_controller_state
-            .as_ref()
-            .is_some()
+        let is_enabled = |node: &str| !disabled_nodes.contains(node);
+        if is_enabled("RuleObstacleComposer")
+            && own_database
+                .main_outputs
+                .game_controller_state
+                .as_ref()
+                .is_some()
         {
             let main_outputs = {
                 self.rule_obstacle_composer
@@ -114,7 +133,13 @@ where
         } else {
             own_database.main_outputs.rule_obstacles = Default::default();
         }
-        {
+        if is_enabled("RoleAssignment") {
+            // the latest entry at or before the cycle start wins, `None` returns to the configuration
+            let forced_role = forced_roles
+                .range(..=own_database.main_outputs.cycle_time.start_time)
+                .next_back()
+                .and_then(|(_, role)| role.as_ref())
+                .or(configuration.role_assignment.forced_role.as_ref());
             let main_outputs = self
                 .role_assignment
                 .cycle(role_assignment::CycleContext {
@@ -125,7 +150,7 @@ where
                     robot_to_field: own_database.main_outputs.robot_to_field.as_ref(),
                     cycle_time: &own_database.main_outputs.cycle_time,
                     field_dimensions: &configuration.field_dimensions,
-                    forced_role: configuration.role_assignment.forced_role.as_ref(),
+                    forced_role,
                     initial_poses: &configuration.localization.initial_poses,
                     optional_roles: &configuration.behavior.optional_roles,
                     player_number: &configuration.player_number,
@@ -141,8 +166,12 @@ where
             own_database.main_outputs.network_robot_obstacles =
                 main_outputs.network_robot_obstacles.value;
             own_database.main_outputs.role = main_outputs.role.value;
+        } else {
+            own_database.main_outputs.team_ball = Default::default();
+            own_database.main_outputs.network_robot_obstacles = Default::default();
+            own_database.main_outputs.role = Default::default();
         }
-        {
+        if is_enabled("BallStateComposer") {
             let main_outputs = self
                 .ball_state_composer
                 .cycle(ball_state_composer::CycleContext {
@@ -160,9 +189,12 @@ where
                 .wrap_err("failed to execute cycle of node `BallStateComposer`")?;
             own_database.main_outputs.ball_state = main_outputs.ball_state.value;
             own_database.main_outputs.rule_ball_state = main_outputs.rule_ball_state.value;
+        } else {
+            own_database.main_outputs.ball_state = Default::default();
+            own_database.main_outputs.rule_ball_state = Default::default();
         }
 
-        {
+        if is_enabled("ActiveVision") {
             let main_outputs = self
                 .active_vision
                 .cycle(active_vision::CycleContext {
@@ -176,9 +208,12 @@ where
                 .wrap_err("failed to execute cycle of node `ActiveVision`")?;
             own_database.main_outputs.position_of_interest =
                 main_outputs.position_of_interest.value;
+        } else {
+            own_database.main_outputs.position_of_interest = Default::default();
         }
         {
-            if own_database.main_outputs.robot_to_field.as_ref().is_some()
+            if is_enabled("KickSelector")
+                && own_database.main_outputs.robot_to_field.as_ref().is_some()
                 && own_database.main_outputs.ball_position.as_ref().is_some()
             {
                 let main_outputs = {
@@ -227,9 +262,10 @@ where
                     main_outputs.instant_kick_decisions.value;
             } else {
                 own_database.main_outputs.kick_decisions = Default::default();
+                own_database.main_outputs.instant_kick_decisions = Default::default();
             }
         }
-        {
+        if is_enabled("WorldStateComposer") {
             let main_outputs = self
                 .world_state_composer
                 .cycle(world_state_composer::CycleContext {
@@ -258,8 +294,10 @@ where
                 })
                 .wrap_err("failed to execute cycle of node `WorldStateComposer`")?;
             own_database.main_outputs.world_state = main_outputs.world_state.value;
+        } else {
+            own_database.main_outputs.world_state = Default::default();
         }
-        {
+        if is_enabled("Behavior") {
             let main_outputs = self
                 .behavior
                 .cycle(node::CycleContext {
@@ -282,6 +320,8 @@ where
                 })
                 .wrap_err("failed to execute cycle of node `Behavior`")?;
             own_database.main_outputs.motion_command = main_outputs.motion_command.value;
+        } else {
+            own_database.main_outputs.motion_command = Default::default();
         }
         self.own_changed.notify_one();
         Ok(())
@@ -1,4 +1,9 @@
-use std::{collections::BTreeMap, convert::Into, sync::Arc, time::SystemTime};
+use std::{
+    collections::{BTreeMap, HashSet},
+    convert::Into,
+    sync::Arc,
+    time::SystemTime,
+};
 
 use color_eyre::{eyre::WrapErr, Result};
 use control::localization::generate_initial_pose;
@@ -6,7 +11,7 @@ use cyclers::control::Database;
 use parameters::directory::deserialize;
 use spl_network_messages::PlayerNumber;
 use structs::Configuration;
-use types::messages::IncomingMessage;
+use types::{messages::IncomingMessage, Role};
 
 use crate::{cycler::BehaviorCycler, interfake::Interfake};
 
@@ -16,6 +21,8 @@ pub struct Robot {
     pub database: Database,
     pub configuration: Configuration,
     pub is_penalized: bool,
+    pub disabled_nodes: HashSet<String>,
+    pub forced_roles: BTreeMap<SystemTime, Option<Role>>,
 }
 
 impl Robot {
@@ -52,12 +59,19 @@ impl Robot {
             database,
             configuration,
             is_penalized: false,
+            disabled_nodes: HashSet::new(),
+            forced_roles: BTreeMap::new(),
         })
     }
 
     pub fn cycle(&mut self, messages: BTreeMap<SystemTime, Vec<&IncomingMessage>>) -> Result<()> {
-        self.cycler
-            .cycle(&mut self.database, &self.configuration, messages)
+        self.cycler.cycle(
+            &mut self.database,
+            &self.configuration,
+            messages,
+            &self.disabled_nodes,
+            &self.forced_roles,
+        )
     }
 }
 
@@ -1,4 +1,9 @@
-use std::{fs::read_to_string, path::Path, sync::Arc, time::Duration};
+use std::{
+    fs::read_to_string,
+    path::Path,
+    sync::Arc,
+    time::{Duration, UNIX_EPOCH},
+};
 
 use crate::robot::to_player_number;
 use color_eyre::{
@@ -9,9 +14,10 @@ use cyclers::control::Database;
 use mlua::{Error as LuaError, Function, Lua, LuaSerdeExt, SerializeOptions, Value};
 use nalgebra::{Isometry2, Vector2};
 use parking_lot::Mutex;
-use types::Players;
+use types::{Players, Role};
 
 use crate::{
+    cycler::NODE_NAMES,
     robot::Robot,
     state::{Event, LuaRobot, State},
 };
@@ -124,7 +130,102 @@ impl Simulator {
                     Ok(())
                 })?,
             )?;
+            self.lua.globals().set(
+                "disable_node",
+                scope.create_function(|_, (player_number, node): (usize, String)| {
+                    let player_number =
+                        to_player_number(player_number).map_err(LuaError::external)?;
+                    validate_node_name(&node)?;
+                    self.state
+                        .lock()
+                        .robots
+                        .get_mut(&player_number)
+                        .unwrap()
+                        .disabled_nodes
+                        .insert(node);
+
+                    Ok(())
+                })?,
+            )?;
+            self.lua.globals().set(
+                "enable_node",
+                scope.create_function(|_, (player_number, node): (usize, String)| {
+                    let player_number =
+                        to_player_number(player_number).map_err(LuaError::external)?;
+                    validate_node_name(&node)?;
+                    self.state
+                        .lock()
+                        .robots
+                        .get_mut(&player_number)
+                        .unwrap()
+                        .disabled_nodes
+                        .remove(&node);
+
+                    Ok(())
+                })?,
+            )?;
+
+            self.lua.globals().set(
+                "force_role",
+                scope.create_function(
+                    |lua, (player_number, time_elapsed, role): (usize, f32, Value)| {
+                        let player_number =
+                            to_player_number(player_number).map_err(LuaError::external)?;
+                        let role: Role = lua.from_value(role)?;
+
+                        self.state
+                            .lock()
+                            .robots
+                            .get_mut(&player_number)
+                            .unwrap()
+                            .forced_roles
+                            .insert(
+                                UNIX_EPOCH
+                                    + Duration::try_from_secs_f32(time_elapsed)
+                                        .map_err(LuaError::external)?,
+                                Some(role),
+                            );
+
+                        Ok(())
+                    },
+                )?,
+            )?;
+            self.lua.globals().set(
+                "clear_forced_role",
+                scope.create_function(|_, (player_number, time_elapsed): (usize, f32)| {
+                    let player_number =
+                        to_player_number(player_number).map_err(LuaError::external)?;
+
+                    self.state
+                        .lock()
+                        .robots
+                        .get_mut(&player_number)
+                        .unwrap()
+                        .forced_roles
+                        .insert(
+                            UNIX_EPOCH
+                                + Duration::try_from_secs_f32(time_elapsed)
+                                    .map_err(LuaError::external)?,
+                            None,
+                        );
+
+                    Ok(())
+                })?,
+            )?;
+            self.lua.globals().set(
+                "get_main_outputs",
+                scope.create_function(|lua, player_number: usize| {
+                    let player_number =
+                        to_player_number(player_number).map_err(LuaError::external)?;
+                    let state = self.state.lock();
+                    let robot = state
+                        .robots
+                        .get(&player_number)
+                        .ok_or_else(|| LuaError::external(format!("no robot {player_number:?}")))?;
 
+                    lua.to_value_with(&robot.database.main_outputs, SERIALIZE_OPTIONS)
+                })?,
+            )?;
             self.lua.globals().set(
                 "set_robot_pose",
                 scope.create_function(
@@ -195,3 +296,13 @@ impl Simulator {
             .wrap_err("failed to load lua state")
     }
 }
+
+fn validate_node_name(node: &str) -> Result<(), LuaError> {
+    if NODE_NAMES.contains(&node) {
+        Ok(())
+    } else {
+        Err(LuaError::external(format!(
+            "unknown node `{node}`, expected one of {NODE_NAMES:?}"
+        )))
+    }
+}
@@ -16,7 +16,7 @@ use types::{
     OrientationMode, PathSegment, Players, PrimaryState, Side,
 };
 
-use crate::robot::Robot;
+use crate::robot::{from_player_number, Robot};
 
 pub enum Event {
     Cycle,
@@ -160,9 +160,15 @@ impl State {
     }
 
     fn cycle_robots(&mut self, now: std::time::SystemTime) -> Result<()> {
-        let incoming_messages = take(&mut self.messages);
+        let messages_of_last_step = take(&mut self.messages);
 
-        for (player_number, robot) in self.robots.iter_mut() {
+        // Robots are cycled in ascending player number order. Every message is delivered exactly
+        // once to every other robot: robots cycled later in the same step receive it immediately,
+        // robots cycled earlier receive it in the next step.
+        let mut robots: Vec<_> = self.robots.iter_mut().collect();
+        robots.sort_by_key(|(player_number, _)| from_player_number(**player_number));
+
+        for (player_number, robot) in robots {
             let robot_to_field = robot
                 .database
                 .main_outputs
@@ -170,12 +176,10 @@ impl State {
                 .as_mut()
                 .expect("simulated robots should always have a known pose");
 
-            let incoming_messages: Vec<_> = incoming_messages
-                .iter()
-                .filter_map(|(sender, message)| {
-                    (sender != player_number).then_some(IncomingMessage::Spl(*message))
-                })
-                .collect();
+            let incoming_messages: Vec<_> =
+                messages_for_receiver(*player_number, &messages_of_last_step, &self.messages)
+                    .map(|message| IncomingMessage::Spl(*message))
+                    .collect();
             let messages = BTreeMap::from_iter([(now, incoming_messages.iter().collect())]);
 
             robot.database.main_outputs.cycle_time.start_time = now;
@@ -246,11 +250,12 @@ impl State {
         self.ball = lua_state.ball;
         self.cycle_count = lua_state.cycle_count;
         for lua_robot in lua_state.robots {
-            let mut robot = Robot::try_new(lua_robot.configuration.player_number)
-                .expect("Creating dummy robot should never fail");
+            let player_number = lua_robot.configuration.player_number;
+            let robot = self.robots.entry(player_number).or_insert_with(|| {
+                Robot::try_new(player_number).expect("Creating dummy robot should never fail")
+            });
             robot.database = lua_robot.database;
             robot.configuration = lua_robot.configuration;
-            self.robots.insert(robot.configuration.player_number, robot);
         }
 
         self.finished = lua_state.finished;
@@ -262,6 +267,22 @@ impl State {
     }
 }
 
+/// Messages a robot receives when it is cycled, see [`State::cycle_robots`] for the ordering
+///
+/// `messages_of_this_step` only contains messages of robots cycled before the receiver.
+fn messages_for_receiver<'message, Message>(
+    receiver: PlayerNumber,
+    messages_of_last_step: &'message [(PlayerNumber, Message)],
+    messages_of_this_step: &'message [(PlayerNumber, Message)],
+) -> impl Iterator<Item = &'message Message> {
+    let own_index = from_player_number(receiver);
+    messages_of_last_step
+        .iter()
+        .filter(move |(sender, _)| from_player_number(*sender) > own_index)
+        .chain(messages_of_this_step)
+        .map(|(_, message)| message)
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct LuaState {
     pub time_elapsed: f32,
@@ -288,3 +309,63 @@ impl LuaRobot {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_message_is_delivered_once_to_every_other_robot() {
+        let robots = [PlayerNumber::Two, PlayerNumber::Four, PlayerNumber::Five];
+        let mut messages_of_last_step = Vec::new();
+        let mut received: HashMap<PlayerNumber, Vec<(usize, PlayerNumber)>> = HashMap::new();
+
+        for step in 0..3 {
+            let mut messages_of_this_step = Vec::new();
+            for receiver in robots {
+                received.entry(receiver).or_default().extend(
+                    messages_for_receiver(receiver, &messages_of_last_step, &messages_of_this_step)
+                        .copied(),
+                );
+                messages_of_this_step.push((receiver, (step, receiver)));
+            }
+            messages_of_last_step = messages_of_this_step;
+        }
+
+        for receiver in robots {
+            let mut expected: Vec<_> = (0..3)
+                .flat_map(|step| robots.map(|sender| (step, sender)))
+                .filter(|(step, sender)| {
+                    *sender != receiver
+                        && (*step < 2 || from_player_number(*sender) < from_player_number(receiver))
+                })
+                .collect();
+            let mut actual = received[&receiver].clone();
+            expected.sort_by_key(|(step, sender)| (*step, from_player_number(*sender)));
+            actual.sort_by_key(|(step, sender)| (*step, from_player_number(*sender)));
+            assert_eq!(actual, expected, "messages received by {receiver:?}");
+        }
+    }
+
+    #[test]
+    fn later_robots_receive_messages_of_the_same_step() {
+        let messages_of_last_step = [(PlayerNumber::Five, "five, last step")];
+        let messages_of_this_step = [(PlayerNumber::Two, "two, this step")];
+
+        let received_by_four: Vec<_> = messages_for_receiver(
+            PlayerNumber::Four,
+            &messages_of_last_step,
+            &messages_of_this_step,
+        )
+        .collect();
+        assert_eq!(received_by_four, [&"five, last step", &"two, this step"]);
+
+        let received_by_five: Vec<_> = messages_for_receiver(
+            PlayerNumber::Five,
+            &messages_of_last_step,
+            &messages_of_this_step,
+        )
+        .collect();
+        assert_eq!(received_by_five, [&"two, this step"]);
+    }
+}
@@ -5,7 +5,9 @@ use communication::client::{Cycler, CyclerOutput};
 use eframe::epaint::{Color32, Stroke};
 use types::{Ball, CandidateEvaluation, Circle};
 
-use crate::{panels::image::overlay::Overlay, value_buffer::ValueBuffer};
+use crate::{panels::image::overlay::Overlay, twix_painter::color_map, value_buffer::ValueBuffer};
+
+const PRECLASSIFIER_CONFIDENCE_RANGE: (f32, f32) = (0.0, 1.0);
 
 pub struct BallDetection {
     balls: ValueBuffer,
@@ -52,9 +54,16 @@ impl Overlay for BallDetection {
             painter.circle_stroke(
                 circle.center,
                 circle.radius,
-                Stroke::new(2.0, Color32::BLUE),
+                Stroke::new(
+                    2.0,
+                    color_map(
+                        candidate.preclassifier_confidence,
+                        PRECLASSIFIER_CONFIDENCE_RANGE,
+                    ),
+                ),
             );
         }
+        painter.color_map_legend("preclassifier confidence", PRECLASSIFIER_CONFIDENCE_RANGE);
 
         let balls: Vec<Ball> = self.balls.require_latest()?;
         for ball in balls.iter() {
@@ -2,8 +2,8 @@ use std::sync::Arc;
 
 use color_eyre::Result;
 use communication::client::{Cycler, CyclerOutput, Output};
-use eframe::epaint::Color32;
-use types::detected_feet::ClusterPoint;
+use eframe::epaint::{Color32, Stroke};
+use types::detected_feet::{ClusterPoint, DetectedFootInImage};
 
 use crate::{
     nao::Nao, panels::image::overlay::Overlay, twix_painter::TwixPainter, value_buffer::ValueBuffer,
@@ -11,6 +11,7 @@ use crate::{
 
 pub struct FeetDetection {
     cluster_points: ValueBuffer,
+    detections_in_image: ValueBuffer,
 }
 
 impl Overlay for FeetDetection {
@@ -24,6 +25,12 @@ impl Overlay for FeetDetection {
                     path: "feet_detection.cluster_points".to_string(),
                 },
             }),
+            detections_in_image: nao.subscribe_output(CyclerOutput {
+                cycler: selected_cycler,
+                output: Output::Additional {
+                    path: "feet_detection.detections_in_image".to_string(),
+                },
+            }),
         }
     }
 
@@ -32,6 +39,16 @@ impl Overlay for FeetDetection {
         for point in cluster_points {
             painter.circle_filled(point.pixel_coordinates.map(|x| x as f32), 3.0, Color32::RED)
         }
+        let detections_in_image: Vec<DetectedFootInImage> =
+            self.detections_in_image.require_latest()?;
+        for detection in detections_in_image {
+            let alpha = (detection.confidence.clamp(0.0, 1.0) * 255.0) as u8;
+            painter.circle_stroke(
+                detection.pixel_coordinates,
+                10.0,
+                Stroke::new(3.0, Color32::from_rgba_unmultiplied(255, 255, 0, alpha)),
+            );
+        }
         Ok(())
     }
 }
@@ -2,11 +2,29 @@ use std::f32::consts::{FRAC_1_SQRT_2, FRAC_PI_2, PI, TAU};
 
 use eframe::{
     egui::{Painter, Response, Sense, Ui},
-    emath::{Pos2, Rect},
-    epaint::{Color32, PathShape, Rounding, Shape, Stroke},
+    emath::{vec2, Align2, Pos2, Rect},
+    epaint::{Color32, FontId, PathShape, Rounding, Shape, Stroke},
 };
 use nalgebra::{point, vector, Isometry2, Point2, Rotation2, SMatrix, Similarity2, Vector2};
-use types::{Arc, Circle, FieldDimensions, Orientation, PathSegment};
+use types::{Arc, Circle, FieldDimensions, FieldHalf, Orientation, PathSegment};
+
+/// Maps `value` within `range` linearly from red (minimum) over yellow to green (maximum)
+///
+/// Values outside of `range` are clamped. An empty range maps values below it to red and all
+/// others to green.
+pub fn color_map(value: f32, range: (f32, f32)) -> Color32 {
+    let (minimum, maximum) = range;
+    let normalized = if maximum > minimum {
+        ((value - minimum) / (maximum - minimum)).clamp(0.0, 1.0)
+    } else if value < minimum {
+        0.0
+    } else {
+        1.0
+    };
+    let red = (2.0 * (1.0 - normalized)).min(1.0);
+    let green = (2.0 * normalized).min(1.0);
+    Color32::from_rgb((red * 255.0) as u8, (green * 255.0) as u8, 0)
+}
 
 pub enum CoordinateSystem {
     RightHand,
@@ -135,53 +153,15 @@ impl TwixPainter {
             line_stroke,
         );
 
-        // Penalty areas
-        self.rect_stroke(
-            point![
-                -field_dimensions.length / 2.0,
-                -field_dimensions.penalty_area_width / 2.0
-            ],
-            point![
-                -field_dimensions.length / 2.0 + field_dimensions.penalty_area_length,
-                field_dimensions.penalty_area_width / 2.0
-            ],
-            line_stroke,
-        );
-        self.rect_stroke(
-            point![
-                field_dimensions.length / 2.0 - field_dimensions.penalty_area_length,
-                -field_dimensions.penalty_area_width / 2.0
-            ],
-            point![
-                field_dimensions.length / 2.0,
-                field_dimensions.penalty_area_width / 2.0
-            ],
-            line_stroke,
-        );
-
-        // Goal areas
-        self.rect_stroke(
-            point![
-                -field_dimensions.length / 2.0,
-                -field_dimensions.goal_box_area_width / 2.0
-            ],
-            point![
-                -field_dimensions.length / 2.0 + field_dimensions.goal_box_area_length,
-                field_dimensions.goal_box_area_width / 2.0
-            ],
-            line_stroke,
-        );
-        self.rect_stroke(
-            point![
-                field_dimensions.length / 2.0 - field_dimensions.goal_box_area_length,
-                -field_dimensions.goal_box_area_width / 2.0
-            ],
-            point![
-                field_dimensions.length / 2.0,
-                field_dimensions.goal_box_area_width / 2.0
-            ],
-            line_stroke,
-        );
+        // Penalty and goal areas
+        for field_half in [FieldHalf::Own, FieldHalf::Opponent] {
+            for [_, goal_line_corner, inner_corner, _] in [
+                field_dimensions.penalty_box_corners(field_half),
+                field_dimensions.goal_box_corners(field_half),
+            ] {
+                self.rect_stroke(goal_line_corner, inner_corner, line_stroke);
+            }
+        }
 
         // Penalty spots
         self.line_segment(
@@ -358,6 +338,49 @@ impl TwixPainter {
         );
     }
 
+    /// Draws the [`color_map`] of `range` as a labeled gradient into the top left corner
+    pub fn color_map_legend(&self, label: &str, range: (f32, f32)) {
+        const STEPS: usize = 32;
+        let size = vec2(128.0, 12.0);
+        let top_left = self.pixel_rect.left_top() + vec2(8.0, 8.0);
+        let step_width = size.x / STEPS as f32;
+        for step in 0..STEPS {
+            let value = range.0 + (range.1 - range.0) * (step as f32 + 0.5) / STEPS as f32;
+            self.painter.rect_filled(
+                Rect::from_min_size(
+                    top_left + vec2(step as f32 * step_width, 0.0),
+                    vec2(step_width, size.y),
+                ),
+                Rounding::none(),
+                color_map(value, range),
+            );
+        }
+
+        let font_id = FontId::monospace(10.0);
+        let text_top_left = top_left + vec2(0.0, size.y + 2.0);
+        self.painter.text(
+            text_top_left,
+            Align2::LEFT_TOP,
+            format!("{:.2}", range.0),
+            font_id.clone(),
+            Color32::WHITE,
+        );
+        self.painter.text(
+            text_top_left + vec2(size.x, 0.0),
+            Align2::RIGHT_TOP,
+            format!("{:.2}", range.1),
+            font_id.clone(),
+            Color32::WHITE,
+        );
+        self.painter.text(
+            top_left + vec2(size.x + 6.0, size.y / 2.0),
+            Align2::LEFT_CENTER,
+            label,
+            font_id,
+            Color32::WHITE,
+        );
+    }
+
     pub fn transform_world_to_pixel(&self, point: Point2<f32>) -> Pos2 {
         let normalized = self.world_to_pixel
             * point![point.x, point.y * self.camera_coordinate_system.y_scale()];
@@ -559,3 +582,31 @@ fn sort_rect(rect: Rect) -> Rect {
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_map_endpoints() {
+        assert_eq!(color_map(0.0, (0.0, 1.0)), Color32::from_rgb(255, 0, 0));
+        assert_eq!(color_map(0.5, (0.0, 1.0)), Color32::from_rgb(255, 255, 0));
+        assert_eq!(color_map(1.0, (0.0, 1.0)), Color32::from_rgb(0, 255, 0));
+        assert_eq!(color_map(-3.0, (-3.0, 5.0)), Color32::from_rgb(255, 0, 0));
+        assert_eq!(color_map(5.0, (-3.0, 5.0)), Color32::from_rgb(0, 255, 0));
+    }
+
+    #[test]
+    fn color_map_clamps_values_outside_of_range() {
+        assert_eq!(color_map(-1.0, (0.0, 1.0)), color_map(0.0, (0.0, 1.0)));
+        assert_eq!(color_map(2.0, (0.0, 1.0)), color_map(1.0, (0.0, 1.0)));
+    }
+
+    #[test]
+    fn color_map_of_empty_range() {
+        assert_eq!(color_map(0.5, (0.5, 0.5)), Color32::from_rgb(0, 255, 0));
+        assert_eq!(color_map(0.7, (0.5, 0.5)), Color32::from_rgb(0, 255, 0));
+        assert_eq!(color_map(0.3, (0.5, 0.5)), Color32::from_rgb(255, 0, 0));
+        assert_eq!(color_map(0.3, (1.0, 0.0)), Color32::from_rgb(255, 0, 0));
+    }
+}